@@ -0,0 +1,724 @@
+use crate::*;
+
+/// DockNode
+/// * A node of `DockLayout`'s dock tree: the serializable model of the whole layout.
+///   * split node: split into `children` in the direction of `is_lateral`. (Look at `DockNode::split`)
+///   * tabs node: a tab group of `panes`, showing the `active` one. (Look at `DockNode::tabs`)
+/// * Docking a pane (`dock()`) creates new splits or moves it into a tab group, and removes splits left with a single child.
+/// 
+/// # Fields
+/// * key: identifies the node. Should be unique in the tree. Keys of new nodes are generated as `dock-{n}`.
+/// * size: length in its parent split. Nodes without it share the rest evenly.
+/// * is_lateral: (split node) are children laid in lateral direction or vertical direction?
+/// * children: (split node) child nodes.
+/// * panes: (tabs node) keys of panes.
+/// * active: (tabs node) index of the shown pane.
+/// 
+/// # Save & Restore
+/// * With the `serde` feature, DockNode is (de)serializable.
+/// * Sizes changed by resizers live in `DockLayout`'s `sizes` signal. Apply them before saving:
+/// 
+/// ```
+/// use sycamore_seoul::{DockNode, DockZone, StyleLength};
+/// let mut model = DockNode::split("root", true, vec![
+///   DockNode::tabs("left", vec!["files"]).with_size(StyleLength::Percent(20.)),
+///   DockNode::tabs("main", vec!["editor", "preview"]),
+/// ]);
+/// model.dock("preview", "main", DockZone::Right);
+/// assert_eq!(model.children.len(), 3);
+/// // model.apply_sizes(&sizes.get_clone());
+/// ```
+/// 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DockNode {
+  pub key: String,
+  pub size: Option<StyleLength>,
+  pub is_lateral: bool,
+  pub children: Vec<DockNode>,
+  pub panes: Vec<String>,
+  pub active: usize,
+}
+
+/// Where to dock a pane in the target tab group
+/// * Left, Right, Top, Bottom: split the target, putting the pane at the side.
+/// * Center: move the pane into the target's tabs.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DockZone {
+  Left,
+  Right,
+  Top,
+  Bottom,
+  Center,
+}
+
+impl DockZone {
+
+  /// Zone of the point(x, y) in the rect(left, top, width, height)
+  /// * Points within 25% of an edge are at the edge's zone, and the others are at the center.
+  /// 
+  pub fn from_point(x: f64, y: f64, left: f64, top: f64, width: f64, height: f64) -> Self {
+    let (rx, ry) = ((x-left)/width, (y-top)/height);
+
+    // distance to each edge, in ratio
+    let edges = [(Self::Left, rx), (Self::Right, 1.-rx), (Self::Top, ry), (Self::Bottom, 1.-ry)];
+    edges.into_iter()
+      .filter(|(_, d)| *d<DOCK_EDGE)
+      .min_by(|a, b| a.1.total_cmp(&b.1))
+      .map(|(zone, _)| zone)
+      .unwrap_or(Self::Center)
+  }
+
+  fn is_lateral(&self) -> bool {
+    matches!(self, Self::Left | Self::Right)
+  }
+
+  fn is_before(&self) -> bool {
+    matches!(self, Self::Left | Self::Top)
+  }
+}
+
+/// Ratio of edge zones
+const DOCK_EDGE: f64 = 0.25;
+
+
+impl DockNode {
+
+  pub fn tabs(key: &str, panes: Vec<&str>) -> Self {
+    Self { key: key.to_string(), size: None, is_lateral: true, children: Vec::new(), panes: panes.into_iter().map(String::from).collect(), active: 0 }
+  }
+
+  pub fn split(key: &str, is_lateral: bool, children: Vec<DockNode>) -> Self {
+    Self { is_lateral, children, ..Self::tabs(key, Vec::new()) }
+  }
+
+  pub fn with_size(self, size: StyleLength) -> Self {
+    Self { size: Some(size), ..self }
+  }
+
+  pub fn is_tabs(&self) -> bool {
+    self.children.is_empty()
+  }
+
+  /// Find the node of the key
+  pub fn find(&self, key: &str) -> Option<&DockNode> {
+    if self.key==key { return Some(self); }
+    self.children.iter().find_map(|x| x.find(key))
+  }
+
+  fn find_mut(&mut self, key: &str) -> Option<&mut DockNode> {
+    if self.key==key { return Some(self); }
+    self.children.iter_mut().find_map(|x| x.find_mut(key))
+  }
+
+  /// Find the tab group having the pane
+  pub fn find_pane(&self, pane: &str) -> Option<&DockNode> {
+    if self.is_tabs() {
+      return self.panes.iter().any(|x| x==pane).then_some(self);
+    }
+    self.children.iter().find_map(|x| x.find_pane(pane))
+  }
+
+  fn find_pane_mut(&mut self, pane: &str) -> Option<&mut DockNode> {
+    if self.is_tabs() {
+      return self.panes.iter().any(|x| x==pane).then_some(self);
+    }
+    self.children.iter_mut().find_map(|x| x.find_pane_mut(pane))
+  }
+
+  /// Apply sizes(keyed by node's key) to nodes. (e.g. `DockLayout`'s sizes, before saving)
+  pub fn apply_sizes(&mut self, sizes: &HashMap<String, StyleLength>) {
+    for child in self.children.iter_mut() {
+      if let Some(size) = sizes.get(&child.key) {
+        child.size = Some(*size);
+      }
+      child.apply_sizes(sizes);
+    }
+  }
+
+  /// Show the pane in its tab group. Return false if there is no such pane.
+  pub fn activate(&mut self, pane: &str) -> bool {
+    let Some(node) = self.find_pane_mut(pane) else { return false };
+    node.active = node.panes.iter().position(|x| x==pane).unwrap_or(0);
+    true
+  }
+
+  /// Remove the pane. Empty tab groups and single-child splits left are removed.
+  /// Return false if there is no such pane.
+  pub fn remove_pane(&mut self, pane: &str) -> bool {
+    let Some(node) = self.find_pane_mut(pane) else { return false };
+    let i = node.panes.iter().position(|x| x==pane).unwrap_or(0);
+    node.panes.remove(i);
+    if node.active>i || node.active>=node.panes.len() {
+      node.active = node.active.saturating_sub(1);
+    }
+    self.normalize();
+    true
+  }
+
+  /// Dock the pane at the zone of the target tab group
+  /// * Splitting in the same direction as the target's parent split adds a sibling, halving the target's size.
+  ///   Otherwise the target is replaced with a new split of the target and the pane.
+  /// * Return false if nothing changes. (e.g. docking a pane into its own tab group, or an unknown target)
+  /// 
+  pub fn dock(&mut self, pane: &str, target: &str, zone: DockZone) -> bool {
+    if !self.can_dock(pane, target, zone) { return false; }
+
+    self.remove_pane(pane);
+
+    if zone==DockZone::Center {
+      let Some(node) = self.find_mut(target) else { return false };
+      node.panes.push(pane.to_string());
+      node.active = node.panes.len()-1;
+      return true;
+    }
+
+    let tabs_key = self.new_key(&[]);
+    let new = Self::tabs(&tabs_key, vec![pane]);
+    let split_key = self.new_key(&[&tabs_key]);
+
+    if self.key==target {
+      let old = std::mem::replace(self, Self::split(&split_key, zone.is_lateral(), Vec::new()));
+      self.size = old.size;
+      self.children = split_pair(old, new, zone);
+      return true;
+    }
+    self.split_at(target, new, zone, &split_key)
+  }
+
+  /// Would `dock()` change anything? Checked without changing (or cloning) the tree.
+  /// 
+  pub fn can_dock(&self, pane: &str, target: &str, zone: DockZone) -> bool {
+    let Some(node) = self.find(target).filter(|x| x.is_tabs()) else { return false };
+    if self.find_pane(pane).is_none() { return false; }
+    !(node.panes.iter().any(|x| x==pane) && (zone==DockZone::Center || node.panes.len()==1))
+  }
+
+  /// Split the target child of this subtree
+  fn split_at(&mut self, target: &str, mut new: DockNode, zone: DockZone, split_key: &str) -> bool {
+    let Some(i) = self.children.iter().position(|x| x.key==target) else {
+      return self.children.iter_mut().any(|x| x.find(target).is_some() && x.split_at(target, new.clone(), zone, split_key));
+    };
+
+    if self.is_lateral==zone.is_lateral() {
+      let half = half_size(self.children[i].size);
+      self.children[i].size = half;
+      new.size = half;
+      self.children.insert(if zone.is_before() { i } else { i+1 }, new);
+    } else {
+      let old = self.children.remove(i);
+      let mut split = Self::split(split_key, zone.is_lateral(), Vec::new());
+      split.size = old.size;
+      split.children = split_pair(old, new, zone);
+      self.children.insert(i, split);
+    }
+    true
+  }
+
+  /// Remove empty tab groups, and replace single-child splits with the child
+  fn normalize(&mut self) {
+    if self.is_tabs() { return; }
+
+    self.children.iter_mut().for_each(|x| x.normalize());
+    let len = self.children.len();
+    self.children.retain(|x| !(x.is_tabs() && x.panes.is_empty()));
+    if self.children.len()<len {
+      fill_sizes(&mut self.children);
+    }
+
+    if self.children.len()==1 {
+      let size = self.size;
+      *self = self.children.remove(0);
+      self.size = size;
+    }
+  }
+
+  /// New key not used in the tree (and not in `used`)
+  fn new_key(&self, used: &[&str]) -> String {
+    (1..).map(|n| format!("dock-{n}"))
+      .find(|x| self.find(x).is_none() && !used.contains(&x.as_str()))
+      .unwrap_or_default()
+  }
+
+  /// The tree without panes(and active ones) of tab groups: what `DockLayout` re-renders the layout on
+  fn structure(&self) -> DockNode {
+    Self {
+      children: self.children.iter().map(|x| x.structure()).collect(),
+      panes: Vec::new(),
+      active: 0,
+      ..self.clone()
+    }
+  }
+
+  /// SplitNode tree of the dock tree. Tab groups are leaf nodes.
+  pub fn to_split(&self) -> SplitNode {
+    let node = if self.is_tabs() {
+      SplitNode::leaf(&self.key)
+    } else {
+      SplitNode::split(&self.key, self.is_lateral, self.children.iter().map(|x| x.to_split()).collect())
+    };
+    SplitNode { initial: self.size, ..node }
+  }
+}
+
+/// Target and the new node in a split, halves each
+fn split_pair(mut old: DockNode, mut new: DockNode, zone: DockZone) -> Vec<DockNode> {
+  old.size = Some(StyleLength::Percent(50.));
+  new.size = Some(StyleLength::Percent(50.));
+  if zone.is_before() { vec![new, old] } else { vec![old, new] }
+}
+
+fn half_size(size: Option<StyleLength>) -> Option<StyleLength> {
+  match size? {
+    StyleLength::Pixel(v) => Some(StyleLength::Pixel(v/2.)),
+    StyleLength::Percent(v) => Some(StyleLength::Percent(v/2.)),
+    _ => None,
+  }
+}
+
+/// Scale percent sizes to fill 100%, if all children have them
+fn fill_sizes(children: &mut [DockNode]) {
+  let percents: Option<Vec<f64>> = children.iter().map(|x| match x.size {
+    Some(StyleLength::Percent(v)) => Some(v),
+    _ => None,
+  }).collect();
+  let Some(percents) = percents else { return };
+  let sum: f64 = percents.iter().sum();
+  if sum<=0. { return; }
+
+  children.iter_mut().zip(percents).for_each(|(x, v)| x.size = Some(StyleLength::Percent(v/sum*100.)));
+}
+
+
+/// CSS needed by `DockLayout`, in addition to `SPLIT_CSS`
+/// * Override classes of `dock-` prefix to customize.
+/// 
+pub const DOCK_CSS: &str = "\
+.dock-tabs { display: flex; flex-direction: column; width: 100%; height: 100%; }
+.dock-tabbar { display: flex; flex: 0 0 auto; overflow: hidden; }
+.dock-tab { padding: 2px 8px; border: none; background-color: transparent; cursor: grab; touch-action: none; user-select: none; }
+.dock-tab-active { background-color: rgba(10, 120, 250, 0.15); }
+.dock-pane { flex: 1 1 auto; min-height: 0; overflow: hidden; }
+.dock-indicator { position: fixed; z-index: 1000; pointer-events: none; box-sizing: border-box; background-color: rgba(10, 120, 250, 0.2); border: 2px solid rgba(10, 120, 250, 0.6); }
+";
+
+
+/// Ongoing drag of a pane's tab
+/// * pane: (pointer id, pane key)
+/// * target: (tab group key, zone) under the pointer
+/// * lock: locks the document while dragging, like the other drags. (Look at `DragLock`)
+/// 
+struct DockDrag {
+  pane: RefCell<Option<(i32, String)>>,
+  target: RefCell<Option<(String, DockZone)>>,
+  indicator: RefCell<Option<HtmlElement>>,
+  lock: DragLock,
+}
+
+impl DockDrag {
+
+  fn new() -> Self {
+    Self { pane: RefCell::new(None), target: RefCell::new(None), indicator: RefCell::new(None), lock: DragLock::new("grabbing", false) }
+  }
+
+  /// Start dragging the pane by the pointer
+  fn start(&self, pointer_id: i32, pane: String) {
+    *self.pane.borrow_mut() = Some((pointer_id, pane));
+    self.lock.lock();
+  }
+
+  /// Is the pointer dragging a pane?
+  fn is_dragging(&self, pointer_id: i32) -> bool {
+    self.pane.borrow().as_ref().map(|x| x.0) == Some(pointer_id)
+  }
+
+  /// Find the target under the pointer, and show the indicator at its zone
+  fn step(&self, model: Signal<DockNode>, x: f64, y: f64) {
+    let Some((_, pane)) = self.pane.borrow().clone() else { return };
+
+    let group = gloo_utils::document().element_from_point(x as f32, y as f32)
+      .and_then(|x| x.closest("[data-dock]").ok().flatten());
+    let target = group.as_ref().and_then(|group| {
+      let key = group.unchecked_ref::<HtmlElement>().dataset().get("dock")?;
+      let rect = group.get_bounding_client_rect();
+      let zone = DockZone::from_point(x, y, rect.left(), rect.top(), rect.width(), rect.height());
+
+      // skip docking which changes nothing
+      let dockable = model.with_untracked(|m| m.can_dock(&pane, &key, zone));
+      dockable.then_some((key, zone, rect))
+    });
+
+    match target {
+      Some((key, zone, rect)) => {
+        let (left, top, width, height) = match zone {
+          DockZone::Left => (rect.left(), rect.top(), rect.width()/2., rect.height()),
+          DockZone::Right => (rect.left()+rect.width()/2., rect.top(), rect.width()/2., rect.height()),
+          DockZone::Top => (rect.left(), rect.top(), rect.width(), rect.height()/2.),
+          DockZone::Bottom => (rect.left(), rect.top()+rect.height()/2., rect.width(), rect.height()/2.),
+          DockZone::Center => (rect.left(), rect.top(), rect.width(), rect.height()),
+        };
+        self.show_indicator(left, top, width, height);
+        *self.target.borrow_mut() = Some((key, zone));
+      },
+      None => {
+        self.hide_indicator();
+        *self.target.borrow_mut() = None;
+      }
+    }
+  }
+
+  /// End the drag. Return (pane, target) to dock, if any.
+  fn end(&self) -> Option<(String, Option<(String, DockZone)>)> {
+    self.hide_indicator();
+    self.lock.unlock();
+    let target = self.target.take();
+    self.pane.take().map(|(_, pane)| (pane, target))
+  }
+
+  fn show_indicator(&self, left: f64, top: f64, width: f64, height: f64) {
+    let mut indicator = self.indicator.borrow_mut();
+    if indicator.is_none() {
+      let document = gloo_utils::document();
+      let Some(element) = document.create_element("div").ok().map(|x| x.unchecked_into::<HtmlElement>()) else { return };
+      element.set_class_name("dock-indicator");
+      let Some(body) = document.body() else { return };
+      body.append_child(&element).unwrap_throw();
+      *indicator = Some(element);
+    }
+    let Some(element) = indicator.as_ref() else { return };
+
+    let style = element.style();
+    for (property, value) in [("left", left), ("top", top), ("width", width), ("height", height)] {
+      style.set_property(property, &format!("{:.2}px", value)).unwrap_throw();
+    }
+  }
+
+  fn hide_indicator(&self) {
+    if let Some(element) = self.indicator.take() {
+      element.remove();
+    }
+  }
+}
+
+
+#[derive(Props)]
+pub struct DockLayoutProps<G: Html, F>
+where F: Fn(&str) -> View<G> + 'static
+{
+  model: Signal<DockNode>,
+  view: F,
+  sizes: Option<Signal<HashMap<String, StyleLength>>>,
+}
+
+/// DockLayout
+/// * IDE-like docking layout built on `SplitLayout`: splits are resized by parcels type resizers.
+/// * Each tab group renders a tab bar and its active pane. Each tab group gets `data-dock` dataset of its key.
+/// * Dragging a tab onto a tab group docks the pane: at the edges it creates a new split, and at the center it joins the tab group.
+///   A drop indicator (`dock-indicator`) shows the zone. Escape cancels the drag, and a click on a tab shows its pane.
+/// 
+/// # Props
+/// * model: Signal<DockNode>: the dock tree. Docking updates it, and writes to it are applied to the layout:
+///   * Only changes of the tree's structure (splits, tab groups and sizes) re-render the layout.
+///   * Switching tabs, or moving panes between existing tab groups, keeps the layout. Panes of a tab group stay mounted while hidden.
+/// * view: F (Fn(&str) -> View<G>): view of each pane, by its key.
+/// * sizes: Option<Signal<HashMap<String, StyleLength>>>: sizes of nodes, keyed by node's key.
+///   They are applied to the model on docking. Apply them yourself before saving the model. (Look at `DockNode::apply_sizes`)
+/// 
+/// The layout fills its parent element (width/height 100%).
+/// 
+#[component]
+pub fn DockLayout<G: Html, F>(props: DockLayoutProps<G, F>) -> View<G>
+where F: Fn(&str) -> View<G> + 'static
+{
+  let model = props.model;
+  let sizes = props.sizes.unwrap_or(create_signal(HashMap::new()));
+  let view: Rc<dyn Fn(&str) -> View<G>> = Rc::new(props.view);
+  let drag = Rc::new(DockDrag::new());
+
+  // dock the dragged pane, or show it if it's not dragged onto any target
+  let dock_pane = move |pane: String, target: Option<(String, DockZone)>| {
+    model.update(|m| match target {
+      Some((target, zone)) => {
+        m.apply_sizes(&sizes.get_clone_untracked());
+        m.dock(&pane, &target, zone);
+      },
+      None => { m.activate(&pane); },
+    });
+  };
+
+  // Escape cancels the ongoing drag
+  on_mount({
+    let drag = drag.clone();
+    move || {
+      let cb = Closure::<dyn FnMut(_)>::new(move |e: KeyboardEvent| {
+        if e.key()!="Escape" || drag.pane.borrow().is_none() { return; }
+        e.prevent_default();
+        drag.end();
+      });
+
+      let document = gloo_utils::document();
+      document.add_event_listener_with_callback("keydown", cb.as_ref().unchecked_ref()).unwrap_throw();
+
+      on_cleanup(move || {
+        document.remove_event_listener_with_callback("keydown", cb.as_ref().unchecked_ref()).unwrap_throw();
+      });
+    }
+  });
+
+  // view of each tab group
+  // * Panes and the active one are read from the model reactively, so switching tabs or moving panes between groups doesn't rebuild the layout.
+  // * Panes of a group are kept mounted (keyed by pane's key), and only the active one is shown.
+  let leaf = move |key: &str| {
+    let key = key.to_string();
+    let panes = create_selector({
+      let key = key.clone();
+      move || model.with(|m| m.find(&key).map(|x| x.panes.clone()).unwrap_or_default())
+    });
+    let active = create_selector({
+      let key = key.clone();
+      move || model.with(|m| m.find(&key).and_then(|x| x.panes.get(x.active).cloned()))
+    });
+
+    let tab = {
+      let drag = drag.clone();
+      move |pane: String| {
+        let (label, data) = (pane.clone(), pane.clone());
+        let is_active = { let pane = pane.clone(); move || active.with(|x| x.as_ref()==Some(&pane)) };
+        let (drag_down, drag_move, drag_up, drag_cancel, drag_lost) = (drag.clone(), drag.clone(), drag.clone(), drag.clone(), drag.clone());
+
+        view! {
+          button(class=if is_active() { "dock-tab dock-tab-active" } else { "dock-tab" }, data-dock-pane=data,
+            on:pointerdown=move |e: PointerEvent| {
+              if e.button()!=0 || drag_down.pane.borrow().is_some() { return; }
+              if let Some(target) = e.current_target().map(|x| x.unchecked_into::<Element>()) {
+                let _ = target.set_pointer_capture(e.pointer_id());
+              }
+              drag_down.start(e.pointer_id(), pane.clone());
+            },
+            on:pointermove=move |e: PointerEvent| {
+              if !drag_move.is_dragging(e.pointer_id()) { return; }
+              drag_move.step(model, e.client_x() as f64, e.client_y() as f64);
+            },
+            on:pointerup=move |e: PointerEvent| {
+              if !drag_up.is_dragging(e.pointer_id()) { return; }
+              if let Some((pane, target)) = drag_up.end() {
+                dock_pane(pane, target);
+              }
+            },
+            // pointercancel and lostpointercapture (e.g. the tab is re-rendered) cancel
+            on:pointercancel=move |e: PointerEvent| {
+              if !drag_cancel.is_dragging(e.pointer_id()) { return; }
+              drag_cancel.end();
+            },
+            on:lostpointercapture=move |e: PointerEvent| {
+              if !drag_lost.is_dragging(e.pointer_id()) { return; }
+              drag_lost.end();
+            },
+          ) { (label) }
+        }
+      }
+    };
+
+    let pane_view = {
+      let view = view.clone();
+      move |pane: String| {
+        let content = view(&pane);
+        view! {
+          div(class="dock-pane", style=if active.with(|x| x.as_ref()==Some(&pane)) { "" } else { "display: none;" }) {
+            (content)
+          }
+        }
+      }
+    };
+
+    view! {
+      div(class="dock-tabs", data-dock=key) {
+        div(class="dock-tabbar") {
+          Keyed(iterable=panes, view=tab, key=|x| x.clone())
+        }
+        Keyed(iterable=panes, view=pane_view, key=|x| x.clone())
+      }
+    }
+  };
+
+  // the layout is rebuilt only when the tree's structure changes (splits and tab groups, not panes in them)
+  let structure = create_selector(move || model.with(|m| m.structure()));
+
+  view! {
+    style { (DOCK_CSS) }
+    div(class="split-root") {
+      (structure.with(|m| {
+        let leaf = leaf.clone();
+        view! { SplitLayout(tree=m.to_split(), view=leaf, sizes=sizes) }
+      }))
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn percent(node: &DockNode) -> f64 {
+    match node.size {
+      Some(StyleLength::Percent(v)) => v,
+      _ => panic!("no percent size: {}", node.key),
+    }
+  }
+
+  #[test]
+  fn dock_into_root_keeps_target_key() {
+    let mut model = DockNode::tabs("root", vec!["a", "b"]);
+    assert!(model.dock("b", "root", DockZone::Right));
+
+    assert_eq!(model.key, "dock-2");
+    assert!(model.is_lateral);
+    assert_eq!(model.children.len(), 2);
+    assert_eq!(model.children[0].key, "root");
+    assert_eq!(model.children[0].panes, vec!["a"]);
+    assert_eq!(model.children[1].key, "dock-1");
+    assert_eq!(model.children[1].panes, vec!["b"]);
+    assert_eq!(percent(&model.children[0]), 50.);
+  }
+
+  #[test]
+  fn can_dock_matches_dock() {
+    let model = DockNode::split("root", true, vec![
+      DockNode::tabs("left", vec!["files"]),
+      DockNode::tabs("main", vec!["editor", "preview"]),
+    ]);
+    let zones = [DockZone::Left, DockZone::Right, DockZone::Top, DockZone::Bottom, DockZone::Center];
+    for pane in ["files", "editor", "preview", "unknown"] {
+      for target in ["root", "left", "main", "unknown"] {
+        for zone in zones {
+          assert_eq!(model.can_dock(pane, target, zone), model.clone().dock(pane, target, zone), "{pane} {target} {zone:?}");
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn dock_nothing_to_change() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a"]),
+      DockNode::tabs("r", vec!["b", "c"]),
+    ]);
+    let before = model.clone();
+    assert!(!model.dock("a", "l", DockZone::Left));
+    assert!(!model.dock("b", "r", DockZone::Center));
+    assert!(!model.dock("b", "unknown", DockZone::Center));
+    assert!(!model.dock("unknown", "l", DockZone::Center));
+    assert!(!model.dock("a", "root", DockZone::Center));
+    assert_eq!(model, before);
+  }
+
+  #[test]
+  fn dock_in_same_direction_adds_sibling() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a"]).with_size(StyleLength::Percent(30.)),
+      DockNode::tabs("r", vec!["b", "c"]).with_size(StyleLength::Percent(70.)),
+    ]);
+    assert!(model.dock("c", "r", DockZone::Right));
+
+    let keys: Vec<&str> = model.children.iter().map(|x| x.key.as_str()).collect();
+    assert_eq!(keys, vec!["l", "r", "dock-1"]);
+    assert_eq!(percent(&model.children[1]), 35.);
+    assert_eq!(percent(&model.children[2]), 35.);
+  }
+
+  #[test]
+  fn dock_across_direction_replaces_target_with_split() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a"]).with_size(StyleLength::Percent(30.)),
+      DockNode::tabs("r", vec!["b", "c"]).with_size(StyleLength::Percent(70.)),
+    ]);
+    assert!(model.dock("c", "r", DockZone::Top));
+
+    let split = &model.children[1];
+    assert_eq!(split.key, "dock-2");
+    assert!(!split.is_lateral);
+    assert_eq!(percent(split), 70.);
+    assert_eq!(split.children[0].panes, vec!["c"]);
+    assert_eq!(split.children[1].key, "r");
+    assert_eq!(split.children[1].panes, vec!["b"]);
+  }
+
+  #[test]
+  fn dock_center_moves_pane_and_activates_it() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a", "b"]),
+      DockNode::tabs("r", vec!["c"]),
+    ]);
+    assert!(model.dock("a", "r", DockZone::Center));
+
+    let r = model.find("r").unwrap();
+    assert_eq!(r.panes, vec!["c", "a"]);
+    assert_eq!(r.active, 1);
+    assert_eq!(model.find("l").unwrap().panes, vec!["b"]);
+  }
+
+  #[test]
+  fn remove_pane_collapses_single_child_split() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a"]),
+      DockNode::split("main", false, vec![
+        DockNode::tabs("t", vec!["b"]),
+        DockNode::tabs("bottom", vec!["c"]),
+      ]).with_size(StyleLength::Percent(70.)),
+    ]);
+
+    // main is left with a single child: replaced with it, keeping main's size
+    assert!(model.remove_pane("c"));
+    assert_eq!(model.children[1].key, "t");
+    assert_eq!(percent(&model.children[1]), 70.);
+
+    // root too: replaced with the last tab group
+    assert!(model.remove_pane("a"));
+    assert_eq!(model.key, "t");
+    assert!(model.is_tabs());
+    assert_eq!(model.panes, vec!["b"]);
+    assert!(!model.remove_pane("a"));
+  }
+
+  #[test]
+  fn remove_pane_rescales_sizes() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("a", vec!["a"]).with_size(StyleLength::Percent(20.)),
+      DockNode::tabs("b", vec!["b"]).with_size(StyleLength::Percent(30.)),
+      DockNode::tabs("c", vec!["c"]).with_size(StyleLength::Percent(50.)),
+    ]);
+    assert!(model.remove_pane("b"));
+
+    assert_eq!(model.children.len(), 2);
+    assert!((percent(&model.children[0]) - 20./70.*100.).abs()<1e-9);
+    assert!((percent(&model.children[1]) - 50./70.*100.).abs()<1e-9);
+  }
+
+  #[test]
+  fn remove_pane_keeps_active_pane() {
+    let mut model = DockNode::tabs("t", vec!["a", "b", "c"]);
+    model.activate("c");
+    assert!(model.remove_pane("a"));
+    assert_eq!(model.panes[model.active], "c");
+    assert!(model.remove_pane("c"));
+    assert_eq!(model.active, 0);
+  }
+
+  #[test]
+  fn structure_ignores_panes() {
+    let mut model = DockNode::split("root", true, vec![
+      DockNode::tabs("l", vec!["a", "b"]),
+      DockNode::tabs("r", vec!["c"]),
+    ]);
+    let structure = model.structure();
+    model.activate("b");
+    assert_eq!(model.structure(), structure);
+    model.dock("a", "r", DockZone::Center);
+    assert_eq!(model.structure(), structure);
+    model.dock("b", "r", DockZone::Bottom);
+    assert_ne!(model.structure(), structure);
+  }
+}
@@ -0,0 +1,75 @@
+use crate::*;
+
+/// Document-wide cursor and text-selection lock of a drag session
+/// * Every drag of the crate (`Resizer`, `CornerResizer`, `ParcelsReorder`, `ScrollBar` and `DockLayout`'s tabs) locks the document from pointerdown until the drag ends or is cancelled.
+///   The cursor is each type's `drag_cursor`, or its default.
+/// * While locked, every element shows the drag's cursor and no text gets selected(`user-select: none`),
+///   even when the pointer leaves the thin handle.
+/// * A `<style>` element is added to the document's head on `lock()`, and removed on `unlock()` (or drop).
+///   So the document's own styles are restored as they were.
+/// * shield: also put up a full-viewport transparent overlay while locked.
+///   Iframes and embedded contents under the pointer would take pointer events away from the document, and the overlay covers them.
+/// 
+pub(crate) struct DragLock {
+  cursor: &'static str,
+  shield: bool,
+  style: RefCell<Option<Element>>,
+  overlay: RefCell<Option<Element>>,
+}
+
+impl DragLock {
+
+  pub(crate) fn new(cursor: &'static str, shield: bool) -> Self {
+    Self { cursor, shield, style: RefCell::new(None), overlay: RefCell::new(None) }
+  }
+
+  /// Default cursor of resizing in the direction
+  pub(crate) fn resize_cursor(is_lateral: bool) -> &'static str {
+    if is_lateral { "col-resize" } else { "row-resize" }
+  }
+
+  pub(crate) fn lock(&self) {
+    if self.style.borrow().is_some() { return; }
+
+    let document = gloo_utils::document();
+    let Some(head) = document.head() else { return };
+    let Ok(style) = document.create_element("style") else { return };
+    style.set_text_content(Some(&format!(
+      "* {{ cursor: {} !important; user-select: none !important; -webkit-user-select: none !important; }}",
+      self.cursor
+    )));
+
+    if head.append_child(&style).is_ok() {
+      *self.style.borrow_mut() = Some(style);
+    }
+
+    if self.shield {
+      let Some(body) = document.body() else { return };
+      let Ok(overlay) = document.create_element("div") else { return };
+      overlay.set_attribute("style", SHIELD_STYLE).unwrap_throw();
+      overlay.set_attribute("aria-hidden", "true").unwrap_throw();
+
+      if body.append_child(&overlay).is_ok() {
+        *self.overlay.borrow_mut() = Some(overlay);
+      }
+    }
+  }
+
+  pub(crate) fn unlock(&self) {
+    if let Some(style) = self.style.take() {
+      style.remove();
+    }
+    if let Some(overlay) = self.overlay.take() {
+      overlay.remove();
+    }
+  }
+}
+
+/// Style of the shield overlay: transparent, over everything in the viewport
+const SHIELD_STYLE: &str = "position: fixed; top: 0; left: 0; width: 100vw; height: 100vh; z-index: 2147483647; background-color: transparent;";
+
+impl Drop for DragLock {
+  fn drop(&mut self) {
+    self.unlock();
+  }
+}
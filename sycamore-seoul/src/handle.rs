@@ -0,0 +1,177 @@
+use crate::*;
+
+/// Event listener owned by `Listeners`
+/// 
+struct Listener {
+  target: EventTarget,
+  event: &'static str,
+  passive: bool,
+  cb: Box<dyn AsRef<JsValue>>,
+}
+
+impl Listener {
+
+  fn attach(&self) {
+    let cb = (*self.cb).as_ref().unchecked_ref();
+    if self.passive {
+      let options = AddEventListenerOptions::new();
+      options.set_passive(true);
+      self.target.add_event_listener_with_callback_and_add_event_listener_options(self.event, cb, &options).unwrap_throw();
+    } else {
+      self.target.add_event_listener_with_callback(self.event, cb).unwrap_throw();
+    }
+  }
+
+  fn detach(&self) {
+    self.target.remove_event_listener_with_callback(self.event, (*self.cb).as_ref().unchecked_ref()).unwrap_throw();
+  }
+}
+
+
+/// Observer owned by `Listeners`: observing while attached
+/// * connect: (re)start observing. disconnect: stop observing.
+///   Both own the observer and its callback, which are dropped with them.
+/// 
+struct Observer {
+  connect: Box<dyn Fn()>,
+  disconnect: Box<dyn Fn()>,
+}
+
+
+/// Listeners shared by a handle and its setter
+/// * The setter adds listeners on mount, and keeps them until the scope is cleaned up. (Look at `keep_until_cleanup`)
+/// * observers: (e.g. `ResizeObserver`) disconnected and connected again together with listeners.
+/// * attached: is it (supposed to be) attached? Listeners added while detached are attached on `reattach()`.
+/// * on_detach: hooks called on detach, e.g. to end an ongoing drag.
+/// 
+pub(crate) struct Listeners {
+  list: RefCell<Vec<Listener>>,
+  observers: RefCell<Vec<Observer>>,
+  attached: Cell<bool>,
+  on_detach: RefCell<Vec<Box<dyn Fn()>>>,
+}
+
+impl Listeners {
+
+  /// New listeners, switched on and off by `enabled` signal if given
+  /// 
+  pub(crate) fn new(enabled: Option<ReadSignal<bool>>) -> Rc<Self> {
+    let listeners = Rc::new(Self { list: RefCell::new(Vec::new()), observers: RefCell::new(Vec::new()), attached: Cell::new(true), on_detach: RefCell::new(Vec::new()) });
+
+    if let Some(enabled) = enabled {
+      let weak = Rc::downgrade(&listeners);
+      create_effect(on(enabled, move || {
+        let Some(listeners) = weak.upgrade() else { return };
+        if enabled.get() { listeners.reattach(); } else { listeners.detach(); }
+      }));
+    }
+    listeners
+  }
+
+  /// Own the closure as a listener of target's event
+  /// 
+  pub(crate) fn listen<T: ?Sized + 'static>(&self, target: &EventTarget, event: &'static str, cb: Closure<T>, passive: bool) {
+    let listener = Listener { target: target.clone(), event, passive, cb: Box::new(cb) };
+    if self.attached.get() {
+      listener.attach();
+    }
+    self.list.borrow_mut().push(listener);
+  }
+
+  /// Own the observer, given as its connect & disconnect closures
+  /// 
+  pub(crate) fn observe(&self, connect: impl Fn() + 'static, disconnect: impl Fn() + 'static) {
+    let observer = Observer { connect: Box::new(connect), disconnect: Box::new(disconnect) };
+    if self.attached.get() {
+      (observer.connect)();
+    }
+    self.observers.borrow_mut().push(observer);
+  }
+
+  pub(crate) fn on_detach(&self, f: impl Fn() + 'static) {
+    self.on_detach.borrow_mut().push(Box::new(f));
+  }
+
+  pub(crate) fn detach(&self) {
+    if !self.attached.replace(false) { return; }
+    self.list.borrow().iter().for_each(|x| x.detach());
+    self.observers.borrow().iter().for_each(|x| (x.disconnect)());
+    self.on_detach.borrow().iter().for_each(|f| f());
+  }
+
+  pub(crate) fn reattach(&self) {
+    if self.attached.replace(true) { return; }
+    self.list.borrow().iter().for_each(|x| x.attach());
+    self.observers.borrow().iter().for_each(|x| (x.connect)());
+  }
+
+  pub(crate) fn is_attached(&self) -> bool {
+    self.attached.get()
+  }
+
+  /// Detach and drop all listeners
+  /// 
+  pub(crate) fn clear(&self) {
+    self.detach();
+    self.list.borrow_mut().clear();
+    self.observers.borrow_mut().clear();
+    self.on_detach.borrow_mut().clear();
+  }
+
+  /// Keep listeners until the scope is cleaned up, and clear them then
+  /// * So they don't depend on whether the handle is kept or dropped.
+  /// 
+  pub(crate) fn keep_until_cleanup(listeners: &Rc<Self>) {
+    let listeners = listeners.clone();
+    on_cleanup(move || listeners.clear());
+  }
+}
+
+impl Drop for Listeners {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
+
+
+/// ResizerHandle
+/// * Returned by `Resizer`'s `set_panel_resizer` and `set_parcels_resizer`, and `CornerResizer`'s `set_corner_resizer`.
+/// * Controls the resizer's event listeners:
+///   * `detach()` removes them, and `reattach()` sets them again, while the component stays mounted.
+///   * Listeners live until the scope is cleaned up, whether the handle is kept or not.
+///     Dropping the handle only gives up the control.
+/// * `enabled` field of `Resizer` switches the handle reactively.
+/// 
+#[derive(Clone)]
+pub struct ResizerHandle(pub(crate) Rc<Listeners>);
+
+impl ResizerHandle {
+
+  /// Remove listeners. An ongoing drag ends.
+  pub fn detach(&self) {
+    self.0.detach();
+  }
+
+  /// Set listeners again
+  pub fn reattach(&self) {
+    self.0.reattach();
+  }
+
+  pub fn is_attached(&self) -> bool {
+    self.0.is_attached()
+  }
+}
+
+impl std::fmt::Debug for ResizerHandle {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ResizerHandle").field("attached", &self.is_attached()).finish()
+  }
+}
+
+
+/// ScrollBarHandle
+/// * Returned by `ScrollBar`'s `set_scrollbar`.
+/// * The same handle as `ResizerHandle`, controlling the scrollbar's event listeners.
+/// * `enabled` field of `ScrollBar` switches the handle reactively.
+/// 
+pub type ScrollBarHandle = ResizerHandle;
@@ -0,0 +1,300 @@
+use crate::*;
+use std::collections::VecDeque;
+
+/// LayoutHistory
+/// * Undo/redo history of layout changes made by resizers.
+/// * Give it to `Resizer`'s `history` field (or `history` prop of components). Then each completed drag (and keyboard step) is recorded:
+///   the start and end inline lengths of every element whose length changed, as they were written. (So `calc(...)` or `auto` are kept, too.)
+/// * Clones share the same history, so one history can serve many resizers.
+/// 
+/// # Use
+/// * `undo()`/`redo()` re-apply the inline lengths. (An empty one removes the inline length.)
+///   * And then the resizer which made the change syncs its state with them, as its own update:
+///     `panel_length`/`parcel_lengths` signals are re-emitted, `persist` saves them, and the panel's collapse state is restored.
+/// * `can_undo()`/`can_redo()` signals tell if there is something to undo/redo.
+/// * `bind_keys()` binds Ctrl(Cmd)+Z to undo, and Ctrl(Cmd)+Shift+Z or Ctrl+Y to redo.
+/// 
+/// # Fields
+/// * depth: how many changes are kept. The oldest is dropped when it's full.
+/// 
+#[derive(Clone)]
+pub struct LayoutHistory {
+  inner: Rc<RefCell<HistoryInner>>,
+  can_undo: Signal<bool>,
+  can_redo: Signal<bool>,
+}
+
+struct HistoryInner {
+  depth: usize,
+  undo: VecDeque<HistoryEntry>,
+  redo: Vec<HistoryEntry>,
+}
+
+/// Sync of the resizer's state(signals, persist, collapse) after undo/redo
+/// * Made by the resizer when it records a change. It keeps the state of the moment, and restores it on call.
+/// 
+pub(crate) type HistorySync = Rc<dyn Fn()>;
+
+/// A recorded change: length changes of elements, and syncs of the resizer's state before and after
+/// 
+struct HistoryEntry {
+  changes: Vec<LengthChange>,
+  sync_before: HistorySync,
+  sync_after: HistorySync,
+}
+
+/// Length change of an element: inline values(width/height) as they were written
+/// 
+struct LengthChange {
+  element: HtmlElement,
+  is_lateral: bool,
+  before: String,
+  after: String,
+}
+
+impl LengthChange {
+  fn apply(&self, value: &str) {
+    let property = if self.is_lateral { "width" } else { "height" };
+    if value.is_empty() {
+      let _ = self.element.style().remove_property(property);
+    } else {
+      let _ = self.element.style().set_property(property, value);
+    }
+  }
+}
+
+impl LayoutHistory {
+
+  pub fn new(depth: usize) -> Self {
+    let inner = HistoryInner { depth, undo: VecDeque::new(), redo: Vec::new() };
+    Self { inner: Rc::new(RefCell::new(inner)), can_undo: create_signal(false), can_redo: create_signal(false) }
+  }
+
+  pub fn can_undo(&self) -> ReadSignal<bool> {
+    *self.can_undo
+  }
+
+  pub fn can_redo(&self) -> ReadSignal<bool> {
+    *self.can_redo
+  }
+
+  /// Undo the last change. Return false if there is nothing to undo.
+  /// 
+  pub fn undo(&self) -> bool {
+    let Some(entry) = self.inner.borrow_mut().undo.pop_back() else { return false };
+    entry.changes.iter().for_each(|x| x.apply(&x.before));
+    (entry.sync_before)();
+    self.inner.borrow_mut().redo.push(entry);
+    self.sync();
+    true
+  }
+
+  /// Redo the last undone change. Return false if there is nothing to redo.
+  /// 
+  pub fn redo(&self) -> bool {
+    let Some(entry) = self.inner.borrow_mut().redo.pop() else { return false };
+    entry.changes.iter().for_each(|x| x.apply(&x.after));
+    (entry.sync_after)();
+    self.inner.borrow_mut().undo.push_back(entry);
+    self.sync();
+    true
+  }
+
+  pub fn clear(&self) {
+    let mut inner = self.inner.borrow_mut();
+    inner.undo.clear();
+    inner.redo.clear();
+    drop(inner);
+    self.sync();
+  }
+
+  /// Bind Ctrl(Cmd)+Z to undo, and Ctrl(Cmd)+Shift+Z or Ctrl+Y to redo, on window while the scope lives.
+  /// 
+  pub fn bind_keys(&self) {
+    let history = self.clone();
+
+    on_mount(move || {
+
+      let cb = Closure::<dyn FnMut(_)>::new(move |e: KeyboardEvent| {
+        if !(e.ctrl_key() || e.meta_key()) { return; }
+
+        let done = match e.key().as_str() {
+          "z" | "Z" if e.shift_key() => history.redo(),
+          "z" | "Z" => history.undo(),
+          "y" | "Y" => history.redo(),
+          _ => return,
+        };
+        if done {
+          e.prevent_default();
+        }
+      });
+
+      let window = gloo_utils::window();
+      window.add_event_listener_with_callback("keydown", cb.as_ref().unchecked_ref()).unwrap_throw();
+
+      on_cleanup(move || {
+        window.remove_event_listener_with_callback("keydown", cb.as_ref().unchecked_ref()).unwrap_throw();
+      });
+    });
+  }
+
+  /// Record a change, from inline lengths of elements before and after
+  /// * sync: (before, after) syncs of the resizer's state, called after undo and redo each.
+  /// 
+  pub(crate) fn record(&self, is_lateral: bool, before: &[(HtmlElement, String)], after: &[(HtmlElement, String)], sync: (HistorySync, HistorySync)) {
+    self.record_axes(&[(is_lateral, before, after)], sync);
+  }
+
+  /// Record a change on both axes as one entry (e.g. of `CornerResizer`)
+  /// * axes: (is_lateral, inline lengths before, inline lengths after) of each axis.
+  /// 
+  pub(crate) fn record_axes(&self, axes: &[(bool, &[(HtmlElement, String)], &[(HtmlElement, String)])], (sync_before, sync_after): (HistorySync, HistorySync)) {
+    let changes: Vec<LengthChange> = axes.iter().flat_map(|(is_lateral, before, after)| {
+      before.iter().zip(after.iter()).filter(|((_, x), (_, y))| x!=y)
+        .map(|((element, x), (_, y))| LengthChange {
+          element: element.clone(),
+          is_lateral: *is_lateral,
+          before: x.clone(),
+          after: y.clone(),
+        })
+    }).collect();
+    if changes.is_empty() { return; }
+
+    self.push(HistoryEntry { changes, sync_before, sync_after });
+  }
+
+  /// Push an entry to undo, dropping the oldest one over depth, and clear redo
+  /// 
+  fn push(&self, entry: HistoryEntry) {
+    let mut inner = self.inner.borrow_mut();
+    inner.undo.push_back(entry);
+    while inner.undo.len()>inner.depth {
+      inner.undo.pop_front();
+    }
+    inner.redo.clear();
+    drop(inner);
+    self.sync();
+  }
+
+  fn sync(&self) {
+    let inner = self.inner.borrow();
+    let (can_undo, can_redo) = (!inner.undo.is_empty(), !inner.redo.is_empty());
+    drop(inner);
+
+    if self.can_undo.get_untracked() != can_undo {
+      self.can_undo.set(can_undo);
+    }
+    if self.can_redo.get_untracked() != can_redo {
+      self.can_redo.set(can_redo);
+    }
+  }
+}
+
+impl std::fmt::Debug for LayoutHistory {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let inner = self.inner.borrow();
+    f.debug_struct("LayoutHistory")
+      .field("depth", &inner.depth)
+      .field("undo", &inner.undo.len())
+      .field("redo", &inner.redo.len())
+      .finish()
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Entry without element changes, whose syncs log "-{id}" on undo and "+{id}" on redo
+  fn entry(id: usize, log: &Rc<RefCell<Vec<String>>>) -> HistoryEntry {
+    let (before, after) = (log.clone(), log.clone());
+    HistoryEntry {
+      changes: Vec::new(),
+      sync_before: Rc::new(move || before.borrow_mut().push(format!("-{id}"))),
+      sync_after: Rc::new(move || after.borrow_mut().push(format!("+{id}"))),
+    }
+  }
+
+  fn state(history: &LayoutHistory) -> (bool, bool) {
+    (history.can_undo().get_untracked(), history.can_redo().get_untracked())
+  }
+
+  #[test]
+  fn record_undo_redo() {
+    let _ = create_root(|| {
+      let log = Rc::new(RefCell::new(Vec::new()));
+      let history = LayoutHistory::new(10);
+      assert_eq!(state(&history), (false, false));
+      assert!(!history.undo());
+      assert!(!history.redo());
+
+      history.push(entry(1, &log));
+      history.push(entry(2, &log));
+      assert_eq!(state(&history), (true, false));
+
+      assert!(history.undo());
+      assert_eq!(state(&history), (true, true));
+      assert!(history.undo());
+      assert_eq!(state(&history), (false, true));
+      assert!(!history.undo());
+
+      assert!(history.redo());
+      assert_eq!(state(&history), (true, true));
+      assert!(history.redo());
+      assert_eq!(state(&history), (true, false));
+      assert!(!history.redo());
+
+      assert_eq!(*log.borrow(), ["-2", "-1", "+1", "+2"]);
+    });
+  }
+
+  #[test]
+  fn oldest_dropped_at_depth() {
+    let _ = create_root(|| {
+      let log = Rc::new(RefCell::new(Vec::new()));
+      let history = LayoutHistory::new(2);
+      (1..=3).for_each(|id| history.push(entry(id, &log)));
+
+      while history.undo() {}
+      assert_eq!(*log.borrow(), ["-3", "-2"]);
+      assert_eq!(state(&history), (false, true));
+    });
+  }
+
+  #[test]
+  fn record_clears_redo() {
+    let _ = create_root(|| {
+      let log = Rc::new(RefCell::new(Vec::new()));
+      let history = LayoutHistory::new(10);
+      history.push(entry(1, &log));
+      history.push(entry(2, &log));
+      history.undo();
+      assert_eq!(state(&history), (true, true));
+
+      history.push(entry(3, &log));
+      assert_eq!(state(&history), (true, false));
+      assert!(!history.redo());
+
+      while history.undo() {}
+      assert_eq!(*log.borrow(), ["-2", "-3", "-1"]);
+    });
+  }
+
+  #[test]
+  fn clear_resets_signals() {
+    let _ = create_root(|| {
+      let log = Rc::new(RefCell::new(Vec::new()));
+      let history = LayoutHistory::new(10);
+      history.push(entry(1, &log));
+      history.push(entry(2, &log));
+      history.undo();
+
+      let can_undo = history.can_undo();
+      history.clear();
+      assert!(!can_undo.get_untracked());
+      assert_eq!(state(&history), (false, false));
+      assert!(log.borrow().iter().all(|x| x=="-2"));
+    });
+  }
+}
@@ -1,19 +1,48 @@
+#![allow(clippy::question_mark, clippy::map_flatten, clippy::option_map_unit_fn, clippy::too_many_arguments, clippy::type_complexity)]
+
 mod changeclass;
 pub use changeclass::ChangeClass;
 
 mod stylelength;
-pub use stylelength::StyleLength;
+pub use stylelength::{StyleLength, LengthContext, ParseStyleLengthError};
+
+mod persist;
+pub use persist::{LayoutStorage, LocalStorage, SessionStorage, MemoryStorage, Persist};
+
+mod history;
+pub use history::LayoutHistory;
+use history::HistorySync;
+
+mod draglock;
+use draglock::DragLock;
+
+mod node;
+use node::dom_node;
+
+mod handle;
+pub use handle::{ResizerHandle, ScrollBarHandle};
+use handle::Listeners;
 
 mod resizer;
-pub use resizer::{Resizer, PanelResizerProps, PanelResizer, ParcelsResizerProps, ParcelsResizer};
+pub use resizer::{Resizer, ShrinkStrategy, ResizeEvent, ResizeCallback, PanelResizerProps, PanelResizer, ParcelsResizerProps, ParcelsResizer, CornerResizer, CornerResizerProps, CornerResizerComponent, ParcelsGroup, RebalancePolicy, ParcelsReorder, ParcelsReorderProps, ParcelsReorderComponent};
+use resizer::{POINTER_END_EVENTS, scroll_length};
+
+mod split;
+pub use split::{SplitNode, SplitLayoutProps, SplitLayout, SPLIT_CSS};
+
+mod dock;
+pub use dock::{DockNode, DockZone, DockLayoutProps, DockLayout, DOCK_CSS};
 
 mod scrollbar;
 pub use scrollbar::{ScrollBar, ScrollBarProps, ScrollBarComponent, listen_window_resize_event, sync_scroll_absolute_position};
 
 use sycamore::prelude::*;
 use web_sys::{
-  Element, HtmlElement, Node, Event, EventTarget, MouseEvent, WheelEvent, AddEventListenerOptions
+  Element, HtmlElement, Node, Event, EventTarget, MouseEvent, PointerEvent, KeyboardEvent, WheelEvent, AddEventListenerOptions,
+  ResizeObserver, MutationObserver, MutationObserverInit
 };
 use wasm_bindgen::prelude::*;
 use hashbrown::HashMap;
-use std::str::FromStr;
\ No newline at end of file
+use std::str::FromStr;
+use std::rc::Rc;
+use std::cell::{Cell, RefCell};
\ No newline at end of file
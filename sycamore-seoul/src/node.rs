@@ -0,0 +1,16 @@
+use crate::*;
+
+/// web_sys node of the NodeRef
+/// * Works for `DomNode`, and `HydrateNode` with the `hydrate` feature. So listeners are set on hydration, too.
+/// * None under `SsrNode`, or before the node is mounted.
+/// 
+pub(crate) fn dom_node<G: GenericNode>(rf: NodeRef<G>) -> Option<Node> {
+  if let Some(node) = rf.try_get::<DomNode>() {
+    return Some(node.to_web_sys());
+  }
+  #[cfg(feature = "hydrate")]
+  if let Some(node) = rf.try_get::<sycamore::web::HydrateNode>() {
+    return Some(node.to_web_sys());
+  }
+  None
+}
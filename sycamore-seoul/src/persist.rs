@@ -0,0 +1,242 @@
+use crate::*;
+
+/// LayoutStorage
+/// * Pluggable storage backend of `Persist`.
+/// * There are three backends: `LocalStorage`, `SessionStorage` and `MemoryStorage`.
+///   Implement this trait for other backends. (e.g. cookies, to have persisted lengths in SSR)
+/// * `LocalStorage` and `SessionStorage` are empty outside of the browser, as in SSR.
+/// 
+pub trait LayoutStorage {
+  fn get(&self, key: &str) -> Option<String>;
+  fn set(&self, key: &str, value: &str);
+}
+
+/// window.localStorage
+/// 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalStorage;
+
+impl LayoutStorage for LocalStorage {
+  fn get(&self, key: &str) -> Option<String> {
+    if !cfg!(target_arch = "wasm32") { return None; }
+    gloo_utils::window().local_storage().ok().flatten()?.get_item(key).ok().flatten()
+  }
+
+  fn set(&self, key: &str, value: &str) {
+    if !cfg!(target_arch = "wasm32") { return; }
+    if let Ok(Some(storage)) = gloo_utils::window().local_storage() {
+      let _ = storage.set_item(key, value);
+    }
+  }
+}
+
+/// window.sessionStorage
+/// 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SessionStorage;
+
+impl LayoutStorage for SessionStorage {
+  fn get(&self, key: &str) -> Option<String> {
+    if !cfg!(target_arch = "wasm32") { return None; }
+    gloo_utils::window().session_storage().ok().flatten()?.get_item(key).ok().flatten()
+  }
+
+  fn set(&self, key: &str, value: &str) {
+    if !cfg!(target_arch = "wasm32") { return; }
+    if let Ok(Some(storage)) = gloo_utils::window().session_storage() {
+      let _ = storage.set_item(key, value);
+    }
+  }
+}
+
+/// In-memory storage
+/// * Clones share the same memory.
+/// * Useful for tests, or for keeping layouts only while the app is running.
+/// 
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage(Rc<RefCell<HashMap<String, String>>>);
+
+impl MemoryStorage {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl LayoutStorage for MemoryStorage {
+  fn get(&self, key: &str) -> Option<String> {
+    self.0.borrow().get(key).cloned()
+  }
+
+  fn set(&self, key: &str, value: &str) {
+    self.0.borrow_mut().insert(key.to_string(), value.to_string());
+  }
+}
+
+
+/// Persist
+/// * Layout persistence of `Resizer`.
+/// * Lengths are saved under `key` in `storage` when a resize ends,
+///   and applied to the panel/parcel elements on mount.
+/// 
+/// # Format of saved value
+/// * panel: a StyleLength in its text format. e.g. "20px", "35%", "20px|35%" (Look at `StyleLength`)
+/// * parcels: "parcel-name=StyleLength" pairs joined by ";". e.g. "0=30%;1=70%"
+///   * The parcel-name is the dataset value of each parcel (Look at `parcel_name` of `Resizer::set_parcels_resizer`).
+///     "%", "=" and ";" in it are escaped as "%25", "%3D" and "%3B".
+///   * Malformed pairs are skipped on load, and the others are kept.
+/// 
+/// # Examples
+/// ```
+/// use sycamore_seoul::{Persist, LocalStorage};
+/// let _ = Persist::new("layout-sidebar", LocalStorage);
+/// ```
+#[derive(Clone)]
+pub struct Persist {
+  pub key: String,
+  pub storage: Rc<dyn LayoutStorage>,
+}
+
+impl std::fmt::Debug for Persist {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Persist").field("key", &self.key).finish_non_exhaustive()
+  }
+}
+
+impl Persist {
+
+  pub fn new<S: LayoutStorage + 'static>(key: &str, storage: S) -> Self {
+    Self { key: key.to_string(), storage: Rc::new(storage) }
+  }
+
+  /// Load panel's length
+  /// 
+  pub fn load(&self) -> Option<StyleLength> {
+    self.storage.get(&self.key).and_then(|x| x.parse().ok())
+  }
+
+  /// Save panel's length
+  /// 
+  pub fn save(&self, len: StyleLength) {
+    self.storage.set(&self.key, &len.to_string());
+  }
+
+  /// Load parcels' lengths
+  /// 
+  pub fn load_parcels(&self) -> HashMap<String, StyleLength> {
+    let Some(value) = self.storage.get(&self.key) else { return HashMap::new() };
+
+    value.split(';').filter_map(|pair| {
+      let (name, len) = pair.split_once('=')?;
+      Some((unescape_name(name)?, len.parse().ok()?))
+    }).collect()
+  }
+
+  /// Save parcels' lengths
+  /// 
+  pub fn save_parcels<I>(&self, lens: I)
+  where I: IntoIterator<Item=(String, StyleLength)>
+  {
+    let value = lens.into_iter()
+      .map(|(name, len)| format!("{}={}", escape_name(&name), len))
+      .collect::<Vec<_>>()
+      .join(";");
+    self.storage.set(&self.key, &value);
+  }
+}
+
+
+
+/// Escape "%", "=" and ";" of parcel-name
+/// 
+fn escape_name(name: &str) -> String {
+  name.replace('%', "%25").replace('=', "%3D").replace(';', "%3B")
+}
+
+/// Unescape parcel-name. None if it has an unknown escape.
+/// 
+fn unescape_name(name: &str) -> Option<String> {
+  let mut parts = name.split('%');
+  let mut unescaped = parts.next()?.to_string();
+  for part in parts {
+    let (code, rest) = (part.get(..2)?, &part[2..]);
+    unescaped.push(match code {
+      "25" => '%',
+      "3D" => '=',
+      "3B" => ';',
+      _ => return None,
+    });
+    unescaped.push_str(rest);
+  }
+  Some(unescaped)
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn lengths(pairs: &[(&str, StyleLength)]) -> Vec<(String, StyleLength)> {
+    pairs.iter().map(|(name, len)| (name.to_string(), *len)).collect()
+  }
+
+  #[test]
+  fn panel_round_trip() {
+    let persist = Persist::new("panel", MemoryStorage::new());
+    assert_eq!(persist.load(), None);
+
+    persist.save(StyleLength::PixelPercent(20., 35.));
+    assert_eq!(persist.load(), Some(StyleLength::PixelPercent(20., 35.)));
+  }
+
+  #[test]
+  fn parcels_round_trip() {
+    let persist = Persist::new("parcels", MemoryStorage::new());
+    let saved = lengths(&[("0", StyleLength::Percent(30.)), ("1", StyleLength::Pixel(200.)), ("nav", StyleLength::Rem(12.))]);
+    persist.save_parcels(saved.clone());
+
+    let loaded = persist.load_parcels();
+    assert_eq!(loaded.len(), 3);
+    for (name, len) in saved {
+      assert_eq!(loaded.get(&name), Some(&len));
+    }
+  }
+
+  #[test]
+  fn parcels_names_are_escaped() {
+    let storage = MemoryStorage::new();
+    let persist = Persist::new("parcels", storage.clone());
+    let saved = lengths(&[("a=b", StyleLength::Percent(40.)), ("c;d", StyleLength::Percent(50.)), ("10%", StyleLength::Percent(10.))]);
+    persist.save_parcels(saved.clone());
+
+    assert_eq!(storage.get("parcels").as_deref(), Some("a%3Db=40%;c%3Bd=50%;10%25=10%"));
+    let loaded = persist.load_parcels();
+    assert_eq!(loaded.len(), 3);
+    for (name, len) in saved {
+      assert_eq!(loaded.get(&name), Some(&len));
+    }
+  }
+
+  #[test]
+  fn parcels_malformed_pairs_are_skipped() {
+    let storage = MemoryStorage::new();
+    storage.set("parcels", "a=30%;broken;b=wide;c%zz=10%;d=70%;");
+    let loaded = Persist::new("parcels", storage).load_parcels();
+
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded.get("a"), Some(&StyleLength::Percent(30.)));
+    assert_eq!(loaded.get("d"), Some(&StyleLength::Percent(70.)));
+  }
+
+  #[test]
+  fn parcels_empty_name_and_value() {
+    let storage = MemoryStorage::new();
+    let persist = Persist::new("parcels", storage.clone());
+
+    persist.save_parcels(lengths(&[("", StyleLength::Percent(100.))]));
+    assert_eq!(persist.load_parcels().get(""), Some(&StyleLength::Percent(100.)));
+
+    persist.save_parcels(Vec::new());
+    assert_eq!(storage.get("parcels").as_deref(), Some(""));
+    assert!(persist.load_parcels().is_empty());
+  }
+}
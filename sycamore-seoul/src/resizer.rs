@@ -6,6 +6,23 @@ use panel::*;
 mod parcels;
 use parcels::*;
 
+mod shrink;
+use shrink::{shrink_siblings, shrink_capacity, rebalance_lengths};
+
+mod corner;
+pub use corner::{CornerResizer, CornerResizerProps, CornerResizerComponent};
+
+mod group;
+pub use group::{ParcelsGroup, RebalancePolicy};
+use group::group_parcels;
+
+mod reorder;
+pub use reorder::{ParcelsReorder, ParcelsReorderProps, ParcelsReorderComponent};
+
+mod event;
+pub use event::{ResizeEvent, ResizeCallback};
+use event::ResizeTracker;
+
 mod comps;
 pub use comps::*;
 
@@ -46,6 +63,8 @@ pub use comps::*;
 /// Build the struct in raw format or via method `new()`, and then call either `set_panel_resizer` or `set_parcels_resizer`.
 /// * Using `set_panel_resizer` and `set_parcels_resizer`,
 ///   pass optional arguments to let it know if you want to have some Signals convey infos like is-moving(bool) or newly-updated-length(f64/HashMap<_, f64>)
+/// * Both return `ResizerHandle`, which controls the event listeners. (Look at `ResizerHandle`)
+///   * Listeners live until the scope is cleaned up. Keep the handle only to `detach()`/`reattach()` the resizer.
 /// 
 /// # Sycamore Component
 /// There are Sycamore native component functions wrapping all the logics.
@@ -58,22 +77,101 @@ pub use comps::*;
 /// * to_pixel(bool): set style in pixel or percent?
 /// * min_len: min limitation(Look at `StyleLength`)
 /// * max_len: max limitation(StyleLength)
+///   * Each panel/parcel element can carry its own limitations, which take place of min_len/max_len:
+///     `data-min-len` and `data-max-len` attributes in StyleLength's text format. (e.g. `data-min-len="100px"`)
+///   * So parcels in the same wrap can have different limitations.
+///   * Font/viewport relative limitations are resolved against the document: `Em` is of the root font size (the same as `Rem`),
+///     not of the panel/parcel's own font size.
 /// * change_class_on_move: on moving, convert class from old to new, and vice versa on stop. (Look at `ChangeClass`)
 /// * resizer_rf: the NodeRef of actual resizer element.
+/// * key_step: (step, step with Shift key) of keyboard resizing. Default is (10px, 50px).
+/// * collapse_threshold: (panel only) turns on collapse mode. Dragging the panel under this length collapses it.
+/// * collapse_rail: (panel only) length of collapsed panel. Zero if not given.
+/// * collapsed: (panel only) signal updated if the panel is collapsed or not (collapse mode only).
+/// * shrink_strategy: (parcels only) how facing siblings shrink when a parcel expands. (Look at `ShrinkStrategy`)
+/// * persist: save lengths when a resize ends, and apply them on mount. (Look at `Persist`)
+///   * For parcels type, `parcel_name` is required to identify each parcel.
+/// * controlled: drive lengths from the signal, too. (Look at "Controlled mode")
+/// * enabled: switch the resizer's event listeners on and off reactively. (Look at `ResizerHandle`)
+/// * clamp: pin the length at the limitation, instead of rejecting the move. (Look at "Limitation check")
+/// * clamped: signal updated if the length is pinned at the limitation or not (clamp mode only).
+/// * on_resize_start, on_resize, on_resize_end: resize lifecycle callbacks. (Look at "Lifecycle callbacks")
+/// * history: record each completed drag (and keyboard step) for undo/redo. (Look at `LayoutHistory`)
+/// * responsive: re-check lengths whenever the wrap's size changes. (Look at "Responsive")
+/// * drag_cursor: cursor of the whole document during a drag. Default is `col-resize`(lateral) or `row-resize`(vertical).
+/// * shield: put up a full-viewport transparent overlay during a drag, so iframes under the pointer don't take its events.
+/// 
+/// # Lifecycle callbacks
+/// * on_resize_start: when a drag starts (pointerdown), or before a keyboard step.
+/// * on_resize: at every step which changed the length.
+/// * on_resize_end: when a drag ends (pointerup/pointercancel), or after a keyboard step.
+/// * Each receives `ResizeEvent`: start/current length, delta(px), axis, and (parcels only) per-parcel before/after maps.
+/// 
+/// # Controlled mode
+/// * By default, the resizer only writes to `panel_length`/`parcel_lengths` signals.
+/// * When `controlled` is true, writes to these signals from outside are applied to the DOM as well:
+///   * Each written length goes through the limitation check, and is set with `StyleLength::set_style`. Invalid writes are ignored.
+///   * For parcels type, `parcel_name` is required, and only parcels in the resizer's wrap are affected. (Missing keys are left as they are.)
+///   * Resizer's own updates (dragging, keyboard, etc.) still flow out to the signals, but are not applied back.
+/// 
+/// # Responsive
+/// * Lengths are checked only while dragging by default. So a pixel panel keeps its length when the window shrinks,
+///   and a percent panel scales below its pixel min_len (or over its max_len).
+/// * When `responsive` is true, the wrap's size is observed (`ResizeObserver`), and on every change:
+///   * the panel (or each parcel with inline length) is clamped between its min_len and max_len(and the wrap's length),
+///     with `PixelPercent` limitations meeting both of pixel and percent,
+///   * (parcels) the other parcels with inline length are rebalanced within their limitations, so the wrap stays filled,
+///   * and new lengths are re-emitted on `panel_length`/`parcel_lengths`, and saved to `persist`.
+/// * Resizers in the same wrap share one `ResizeObserver`.
+/// * A collapsed panel is left as it is.
+/// 
+/// # Collapse (panel only)
+/// * When `collapse_threshold` is given:
+///   * Dragging the panel under the threshold snaps it to `collapse_rail` (or zero).
+///   * A collapsed panel stays until dragged over the threshold again, and then expands at least to min_len.
+///   * Double-click on the resizer toggles between collapsed and the last expanded length.
 /// 
 /// # How it works
 /// * All jobs are conducted on the resizer element, which is captured by NodeRef of Sycamore.
-/// * Three event handlers jointly work: mousedown, mousemove, and mouseup
-///   1. On MouseDown of resizer element:
-///      * Add MouseMove and MouseUp event handlers to document.
+/// * Pointer Events are used, so one code path covers mouse, pen and touch.
+/// * Three event handlers jointly work: pointerdown, pointermove, and pointerup(/pointercancel)
+///   1. On PointerDown of resizer element:
+///      * Capture the pointer to the resizer element (`setPointerCapture`), so following events of the pointer keep arriving at it.
 ///      * Do some works to notify initiation of resize, such as to change class of element.
-///   2. MouseMove of document:
+///   2. PointerMove of resizer element:
 ///      * Handle actual resizing:
-///        * capture mouse movement, calculate new length(s), conduct limitation check, and set style of new length(s).
+///        * capture pointer movement, calculate new length(s), conduct limitation check, and set style of new length(s).
 ///      * Do some works to pass new length's info via Signal of Sycamore.
-///   3. MouseUp of document:
-///      * Remove MouseMove and MoueUp event handlers from document. (Raw pointers are used for this purpose. They are handled in secure way.)
+///   3. PointerUp/PointerCancel of resizer element:
+///      * Release the pointer capture.
 ///      * Do works to notify end of resize.
+/// * Escape during a drag cancels it:
+///   * every element touched by the drag gets back the length it had at pointerdown,
+///   * the lengths are re-emitted on the signals, and the drag ends (without saving to `persist`).
+/// * With `shield`, a transparent overlay covers the viewport during a drag.
+///   Pointer events over iframes (or other embedded contents) keep arriving, so the drag doesn't get stuck.
+/// * Only the pointer which started the drag is followed. Other pointers (e.g. a second finger landing mid-drag) are ignored until it is released.
+/// * The resizer element gets {"touch-action: none"}, so touch dragging is not taken over by the browser's panning.
+/// 
+/// # Keyboard & Accessibility
+/// * The resizer element is supposed to be focusable and have separator semantics:
+///   {role="separator" tabindex="0" aria-orientation="vertical(is_lateral)/horizontal"}
+///   * `PanelResizer` and `ParcelsResizer` components render them.
+/// * aria-valuenow/min/max are kept up to date, in percent of the wrapping element's length.
+///   * valuemin/valuemax are taken from min_len/max_len.
+/// * Keydown on the focused resizer element:
+///   * ArrowLeft/ArrowRight(lateral) or ArrowUp/ArrowDown(vertical): move the resizer by `key_step` (larger step with Shift).
+///   * Home/End: resize to the min/max limitation.
+///   * Keyboard resizing goes through the same limitation check as pointer movement.
+/// 
+/// # SSR & Hydration
+/// * Under `SsrNode`, there's no DOM: nothing is measured and no listener is set.
+///   Panels and parcels are not the resizer's own elements, so render their initial lengths with the signals:
+///   * `panel_length`/`parcel_lengths` are seeded with persisted lengths right away (before the first render), if they don't have them yet.
+///   * Bind the panel's (or each parcel's) `style` to it with `StyleLength::inline_style`. e.g. `style=panel_length.get().inline_style(true)`
+///   * `Persist` needs a storage readable on the server, such as cookies. (`LocalStorage` and `SessionStorage` are empty there.)
+/// * With the `hydrate` feature, listeners are set on `HydrateNode` as well, on mount after hydration.
+///   Since the server rendered the same lengths, applying them on mount doesn't move the layout.
 /// 
 /// # Style
 ///   * The resizer element is supposed to be {"position: absolute"} and its parent is NOT to be {"position: static"}
@@ -84,9 +182,13 @@ pub use comps::*;
 ///   * On Mousemove, newly calculated length goes through limitaion check:
 ///     * (1) Is it between [0 ~ wrapping element's length]?
 ///       - (The wrapping element is parent of parent of resizer element).
-///     * (2) Is it between given arguments of min_len and max_len?
-///     * For parcels type, all parcels affected go under this check.
+///     * (2) Is it between given arguments of min_len and max_len? (or the element's own `data-min-len`/`data-max-len`)
+///     * For parcels type, all parcels affected go under this check, each with its own limitations.
 ///   * If check fails, nothing changes.
+///   * In clamp mode (`clamp` is true), the move is cut at the limitation instead: the length is pinned exactly at the boundary.
+///     * The resizer keeps the same offset from the pointer as it had when the drag started.
+///     * For parcels type, the move is also cut at what facing siblings can absorb.
+///     * `clamped` signal tells if the length is pinned at the boundary. It turns false when the drag ends.
 /// 
 /// # About Overflowing of Wrap Element
 /// * The "wrap" element might overflow. And it makes resizing logic very ambiguous.
@@ -105,14 +207,48 @@ pub struct Resizer<G: GenericNode> {
   pub max_len: Option<StyleLength>,
   pub change_class_on_move: Option<(Option<&'static str>, &'static str)>,
   pub resizer_rf: NodeRef<G>,
+  pub key_step: Option<(StyleLength, StyleLength)>,
+  pub collapse_threshold: Option<StyleLength>,
+  pub collapse_rail: Option<StyleLength>,
+  pub collapsed: Option<Signal<bool>>,
+  pub shrink_strategy: ShrinkStrategy,
+  pub persist: Option<Persist>,
+  pub controlled: bool,
+  pub enabled: Option<ReadSignal<bool>>,
+  pub clamp: bool,
+  pub clamped: Option<Signal<bool>>,
+  pub on_resize_start: Option<ResizeCallback>,
+  pub on_resize: Option<ResizeCallback>,
+  pub on_resize_end: Option<ResizeCallback>,
+  pub history: Option<LayoutHistory>,
+  pub responsive: bool,
+  pub drag_cursor: Option<&'static str>,
+  pub shield: bool,
+}
+
+/// ShrinkStrategy
+/// * How facing siblings shrink when a parcel expands. (parcels type only)
+/// 
+/// # Variants
+/// * Nearest: (default) only the nearest sibling which can absorb the whole gap shrinks.
+/// * Cascade: push neighbours one after another, as each reaches its min limitation.
+/// * Proportional: spread the shrink across all facing siblings by their current lengths.
+/// 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShrinkStrategy {
+  #[default] Nearest,
+  Cascade,
+  Proportional,
 }
 
+
 impl<G: GenericNode> Resizer<G> {
 
   /// Build new struct
+  /// * Other fields are set in default. Change them directly if needed.
   /// 
   pub fn new(is_lateral: bool, to_left: bool, to_pixel: bool, min_len: Option<StyleLength>, max_len: Option<StyleLength>, change_class_on_move: Option<(Option<&'static str>, &'static str)>, resizer_rf: NodeRef<G>) -> Self {
-    Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf }
+    Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf, key_step: None, collapse_threshold: None, collapse_rail: None, collapsed: None, shrink_strategy: ShrinkStrategy::default(), persist: None, controlled: false, enabled: None, clamp: false, clamped: None, on_resize_start: None, on_resize: None, on_resize_end: None, history: None, responsive: false, drag_cursor: None, shield: false }
   }
 
   /// Set panel-type resizer's event handlers
@@ -120,31 +256,224 @@ impl<G: GenericNode> Resizer<G> {
   /// # Args (signals)
   /// * moving: update if is it moving(resizing) or not
   /// * panel_length: update the adjusted panel length(px) at every moving step.
+  ///   * If it's `StyleLength::Null`, it's seeded with the measured length on mount. (Look at `StyleLength::from_rect`)
+  ///   * In controlled mode, writes to it are applied to the panel.
   /// * skip_set_style(bool): If it's true, css property wouldn't be changed: only given signals would be updated.
   /// 
+  /// # Return
+  /// * ResizerHandle: detaches and reattaches the event listeners.
+  /// 
   pub fn set_panel_resizer(self, 
     moving: Option<Signal<bool>>, 
     panel_length: Option<Signal<StyleLength>>, 
     skip_set_style: bool
-  ) {
+  ) -> ResizerHandle {
 
     // expand self
-    let Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf } = self;
+    let Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf, key_step, collapse_threshold, collapse_rail, collapsed, persist, controlled, enabled, clamp, clamped, on_resize_start, on_resize, on_resize_end, history, responsive, drag_cursor, shield, .. } = self;
 
-    // mousemove closure
-    let cb_mousemove = Closure::<dyn FnMut(_)>::new(move |e: MouseEvent| {
-      
-      if let Some(style_len) = handle_panel_mousemove(is_lateral, to_left, to_pixel, min_len, max_len, resizer_rf, e, skip_set_style) {
-        if let Some(signal) = panel_length {
+    // resize lifecycle
+    let tracker = ResizeTracker::new(resizer_rf, is_lateral, to_pixel, None, on_resize_start, on_resize, on_resize_end);
+
+    // event listeners, kept until clean-up and controlled by the handle
+    let listeners = Listeners::new(enabled);
+    Listeners::keep_until_cleanup(&listeners);
+
+    // collapse mode
+    let collapse = collapse_threshold.map(|threshold| Rc::new(Collapse::new(threshold, collapse_rail)));
+
+    // clamp mode: is the length pinned at the limitation?
+    let clamp = clamp.then(|| Rc::new(Cell::new(false)));
+
+    // is the resizer itself updating the signal?
+    let emitting = Rc::new(Cell::new(false));
+
+    // seed the signal with the persisted length right away, so the first render (SSR or hydration) has it
+    if let (Some(persist), Some(signal)) = (&persist, panel_length) {
+      if matches!(signal.get_untracked(), StyleLength::Null) {
+        if let Some(style_len) = persist.load() {
           signal.set(style_len);
         }
       }
-    });
+    }
 
-    let cb_mousemove = Box::into_raw(Box::new(cb_mousemove));
+    // apply persisted length on mount
+    if let Some(persist) = persist.clone() {
+      let emitting = emitting.clone();
+      on_mount(move || {
+        let Some(style_len) = persist.load() else { return };
+        if let Some(style_len) = apply_panel_length(is_lateral, min_len, max_len, resizer_rf, style_len, skip_set_style) {
+          if let Some(signal) = panel_length {
+            emit(&emitting, || signal.set(style_len));
+          }
+        }
+      });
+    }
+
+    // seed the signal with the measured length on mount, if it's not given yet
+    if let Some(signal) = panel_length {
+      let emitting = emitting.clone();
+      on_mount(move || {
+        if !matches!(signal.get_untracked(), StyleLength::Null) { return; }
+        let Some(style_len) = resizer_parent_element(resizer_rf).and_then(|x| StyleLength::from_rect(&x, is_lateral, to_pixel)) else { return };
+        emit(&emitting, || signal.set(style_len));
+      });
+    }
+
+    // controlled mode: apply outer writes to the panel
+    if let (true, Some(signal)) = (controlled, panel_length) {
+      let (collapse, emitting) = (collapse.clone(), emitting.clone());
+      on_mount(move || {
+        create_effect(on(signal, move || {
+          if emitting.get() { return; }
+
+          if apply_panel_length(is_lateral, min_len, max_len, resizer_rf, signal.get(), skip_set_style).is_some() {
+            if let Some(collapse) = &collapse {
+              collapse.collapsed.set(false);
+            }
+            sync_collapsed(collapsed, collapse.as_deref());
+            set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+          }
+        }));
+      });
+    }
+
+    // responsive: re-clamp the panel when the wrap's size changes
+    if responsive {
+      let (collapse, emitting, persist) = (collapse.clone(), emitting.clone(), persist.clone());
+      on_mount(move || {
+        observe_wrap(resizer_rf, move || {
+          if collapse.as_ref().map(|x| x.collapsed.get()).unwrap_or(false) { return; }
+          let Some(style_len) = clamp_panel_length(is_lateral, to_pixel, min_len, max_len, resizer_rf, skip_set_style) else { return };
+          if let Some(signal) = panel_length {
+            emit(&emitting, || signal.set(style_len));
+          }
+          if let Some(persist) = &persist {
+            persist.save(style_len);
+          }
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+        });
+      });
+    }
+
+    // resized length, not saved yet
+    let resized = Rc::new(Cell::new(StyleLength::Null));
+
+    // step closure (pointermove & keydown)
+    let cb_step = {
+      let (collapse, resized, emitting, clamp) = (collapse.clone(), resized.clone(), emitting.clone(), clamp.clone());
+      move |step: Step| {
+      
+        if let Some(style_len) = handle_panel_mousemove(is_lateral, to_left, to_pixel, min_len, max_len, resizer_rf, step, skip_set_style, collapse.as_deref(), clamp.as_deref()) {
+          if let Some(signal) = panel_length {
+            emit(&emitting, || signal.set(style_len));
+          }
+          sync_collapsed(collapsed, collapse.as_deref());
+          resized.set(style_len);
+          true
+        } else {
+          false
+        }
+      }
+    };
+
+    // rollback closure (pointerdown & Escape)
+    let cb_rollback = {
+      let (collapse, resized, emitting) = (collapse.clone(), resized.clone(), emitting.clone());
+      let saved = Cell::new((StyleLength::Null, None));
+      move |rollback: Rollback| match rollback {
+        Rollback::Snapshot => {
+          // the panel's length measured at pointerdown (the signal might not have been set yet)
+          let style_len = resizer_parent_element(resizer_rf).and_then(|x| StyleLength::from_rect(&x, is_lateral, to_pixel))
+            .or_else(|| panel_length.map(|x| x.get_untracked()))
+            .unwrap_or_default();
+          saved.set((style_len, collapse.as_ref().map(|x| x.state())));
+        },
+        Rollback::Restore => {
+          let (style_len, state) = saved.get();
+          if let Some(signal) = panel_length {
+            emit(&emitting, || signal.set(style_len));
+          }
+          if let (Some(collapse), Some(state)) = (&collapse, state) {
+            collapse.set_state(state);
+          }
+          sync_collapsed(collapsed, collapse.as_deref());
+          resized.set(StyleLength::Null);
+        },
+      }
+    };
+
+    // history sync closure: keep collapse state, and restore it with the panel's length re-emitted and saved
+    let cb_sync = {
+      let (collapse, emitting, persist) = (collapse.clone(), emitting.clone(), persist.clone());
+      move || -> HistorySync {
+        let state = collapse.as_ref().map(|x| x.state());
+        let (collapse, emitting, persist) = (collapse.clone(), emitting.clone(), persist.clone());
+        Rc::new(move || {
+          if let (Some(collapse), Some(state)) = (&collapse, state) {
+            collapse.set_state(state);
+          }
+          sync_collapsed(collapsed, collapse.as_deref());
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+
+          let Some(style_len) = resizer_parent_element(resizer_rf).and_then(|x| current_length(&x, is_lateral, to_pixel)) else { return };
+          if let Some(signal) = panel_length {
+            emit(&emitting, || signal.set(style_len));
+          }
+          if let Some(persist) = &persist {
+            persist.save(style_len);
+          }
+        })
+      }
+    };
+
+    // end closure (pointerup & keydown)
+    let cb_end = {
+      let persist = persist.clone();
+      move || {
+        let style_len = resized.replace(StyleLength::Null);
+        if let Some(persist) = &persist {
+          if !matches!(style_len, StyleLength::Null) {
+            persist.save(style_len);
+          }
+        }
+      }
+    };
+
+    // double-click toggles collapse
+    if let Some(collapse) = collapse {
+      let listeners = Rc::downgrade(&listeners);
+      on_mount(move || {
+
+        let Some(listeners) = listeners.upgrade() else { return };
+        let Some(target) = dom_node(resizer_rf).map(|x| x.unchecked_into::<EventTarget>()) else { return };
+
+        let cb_dblclick = Closure::<dyn FnMut(_)>::new(move |_: MouseEvent| {
+          if let Some(style_len) = toggle_panel_collapse(is_lateral, to_pixel, min_len, resizer_rf, skip_set_style, &collapse) {
+            if let Some(signal) = panel_length {
+              emit(&emitting, || signal.set(style_len));
+            }
+            sync_collapsed(collapsed, Some(&collapse));
+            set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+            if let Some(persist) = &persist {
+              persist.save(style_len);
+            }
+          }
+        });
+
+        listeners.listen(&target, "dblclick", cb_dblclick, false);
+      });
+    }
+
+    let lock = DragLock::new(drag_cursor.unwrap_or(DragLock::resize_cursor(is_lateral)), shield);
 
     // set each event handlers
-    Self::set_event_handlers(resizer_rf, change_class_on_move, cb_mousemove, moving);
+    Self::set_event_handlers(
+      HandlerSettings { resizer_rf, is_lateral, to_left, min_len, max_len, key_step, change_class_on_move, moving, clamp, clamped, tracker, history, lock, listeners: Rc::downgrade(&listeners) },
+      HandlerCallbacks { step: cb_step, end: cb_end, rollback: cb_rollback, sync: cb_sync },
+    );
+
+    ResizerHandle(listeners)
   }
 
 
@@ -153,104 +482,464 @@ impl<G: GenericNode> Resizer<G> {
   /// # Args (signals)
   /// * moving: update if is it moving(resizing) or not
   /// * parcel_lengths: hashbrown::HashMap collected from <parcel-element's dataset value: adjusted percent length>
+  ///   * Parcels missing in it are seeded with their measured lengths on mount. (Needs parcel_name)
+  ///   * In controlled mode, writes to it are applied to the parcels.
   /// * parcel_name: the name of parcel-element's dataset to identify each parcel.
   /// * skip_set_style(bool): If it's true, css property wouldn't be changed: only given signals would be updated.
   /// 
+  /// # Return
+  /// * ResizerHandle: detaches and reattaches the event listeners.
+  /// 
   pub fn set_parcels_resizer<P>(self, 
     moving: Option<Signal<bool>>, 
     parcel_lengths: Option<Signal<HashMap<P, StyleLength>>>, 
     parcel_name: Option<&'static str>, 
     skip_set_style: bool
-  )
+  ) -> ResizerHandle
   where P: std::cmp::Eq + std::hash::Hash + FromStr
   {
     // expand self
-    let Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf } = self;
+    let Self { is_lateral, to_left, to_pixel, min_len, max_len, change_class_on_move, resizer_rf, key_step, shrink_strategy, persist, controlled, enabled, clamp, clamped, on_resize_start, on_resize, on_resize_end, history, responsive, drag_cursor, shield, .. } = self;
+
+    // resize lifecycle
+    let tracker = ResizeTracker::new(resizer_rf, is_lateral, to_pixel, parcel_name, on_resize_start, on_resize, on_resize_end);
 
-    // mousemove closure
-    let cb_mousemove = Closure::<dyn FnMut(_)>::new(move |e: web_sys::MouseEvent| {
+    // event listeners, kept until clean-up and controlled by the handle
+    let listeners = Listeners::new(enabled);
+    Listeners::keep_until_cleanup(&listeners);
 
-      if let Some(map) = handle_parcels_mousemove(is_lateral, to_left, to_pixel, min_len, max_len, resizer_rf, e, parcel_name, skip_set_style) {
+    // persistence needs parcel_name to identify each parcel
+    let persist = persist.zip(parcel_name);
+
+    // clamp mode: is the length pinned at the limitation?
+    let clamp = clamp.then(|| Rc::new(Cell::new(false)));
+
+    // is the resizer itself updating the signal?
+    let emitting = Rc::new(Cell::new(false));
+
+    // seed the signal with persisted lengths right away (missing keys only), so the first render (SSR or hydration) has them
+    if let (Some((persist, _)), Some(signal)) = (&persist, parcel_lengths) {
+      let lengths: Vec<(P, StyleLength)> = persist.load_parcels().into_iter().filter_map(|(name, style_len)| Some((name.parse::<P>().ok()?, style_len))).collect();
+      if lengths.iter().any(|(key, _)| signal.with_untracked(|map| !map.contains_key(key))) {
+        signal.update(|map| lengths.into_iter().for_each(|(key, style_len)| { map.entry(key).or_insert(style_len); }));
+      }
+    }
+
+    // apply persisted lengths on mount
+    if let Some((persist, parcel_name)) = persist.clone() {
+      let emitting = emitting.clone();
+      on_mount(move || {
+        let lengths = persist.load_parcels();
+        if lengths.is_empty() { return; }
+
+        let Some(map) = apply_parcel_lengths(resizer_rf, is_lateral, min_len, max_len, parcel_name, |name| lengths.get(name).copied(), skip_set_style) else { return };
         if let Some(signal) = parcel_lengths {
-          signal.update(|x| x.extend(map)); // use signal.update() instead of signal.set();
+          emit(&emitting, || signal.update(|x| x.extend(map)));
+        }
+      });
+    }
+
+    // seed the signal with the measured lengths on mount, for parcels not given yet
+    if let (Some(signal), Some(parcel_name)) = (parcel_lengths, parcel_name) {
+      let emitting = emitting.clone();
+      on_mount(move || {
+        let lengths: Vec<(P, StyleLength)> = collect_parcel_lengths(resizer_rf, is_lateral, to_pixel, parcel_name).into_iter()
+          .filter_map(|(name, style_len)| Some((name.parse::<P>().ok()?, style_len)))
+          .filter(|(key, _)| signal.with_untracked(|x| !x.contains_key(key)))
+          .collect();
+        if lengths.is_empty() { return; }
+        emit(&emitting, || signal.update(|x| x.extend(lengths)));
+      });
+    }
+
+    // controlled mode: apply outer writes to the parcels
+    if let (true, Some(signal), Some(parcel_name)) = (controlled, parcel_lengths, parcel_name) {
+      let emitting = emitting.clone();
+      on_mount(move || {
+        create_effect(on(signal, move || {
+          if emitting.get() { return; }
+
+          let applied = signal.with(|lengths| {
+            apply_parcel_lengths::<G, P>(resizer_rf, is_lateral, min_len, max_len, parcel_name, |name| name.parse::<P>().ok().and_then(|p| lengths.get(&p).copied()), skip_set_style)
+          });
+          if applied.is_some() {
+            set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+          }
+        }));
+      });
+    }
+
+    // responsive: re-clamp and rebalance the parcels when the wrap's size changes
+    if responsive {
+      let (emitting, persist) = (emitting.clone(), persist.clone());
+      on_mount(move || {
+        observe_wrap(resizer_rf, move || {
+          let Some(map) = clamp_parcel_lengths::<G, P>(is_lateral, to_pixel, min_len, max_len, resizer_rf, parcel_name, skip_set_style) else { return };
+          if let Some(signal) = parcel_lengths {
+            emit(&emitting, || signal.update(|x| x.extend(map)));
+          }
+          if let Some((persist, parcel_name)) = &persist {
+            persist.save_parcels(collect_parcel_lengths(resizer_rf, is_lateral, to_pixel, parcel_name));
+          }
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+        });
+      });
+    }
+
+    // resized, not saved yet
+    let resized = Rc::new(Cell::new(false));
+
+    // step closure (pointermove & keydown)
+    let cb_step = {
+      let (resized, emitting, clamp) = (resized.clone(), emitting.clone(), clamp.clone());
+      move |step: Step| {
+
+        if let Some(map) = handle_parcels_mousemove(is_lateral, to_left, to_pixel, min_len, max_len, resizer_rf, step, parcel_name, skip_set_style, shrink_strategy, clamp.as_deref()) {
+          if let Some(signal) = parcel_lengths {
+            emit(&emitting, || signal.update(|x| x.extend(map))); // use signal.update() instead of signal.set();
+          }
+          resized.set(true);
+          true
+        } else {
+          false
         }
       }
-    });
+    };
+
+    // rollback closure (Escape): re-emit restored lengths
+    let cb_rollback = {
+      let (resized, emitting) = (resized.clone(), emitting.clone());
+      move |rollback: Rollback| {
+        let Rollback::Restore = rollback else { return };
+        resized.set(false);
+
+        let (Some(signal), Some(parcel_name)) = (parcel_lengths, parcel_name) else { return };
+        let map: HashMap<P, StyleLength> = wrap_parcels(resizer_rf).into_iter().filter_map(|element| {
+          let element = element.unchecked_into::<HtmlElement>();
+          let p = element.dataset().get(parcel_name)?.parse().ok()?;
+          let style_len: StyleLength = element.style().get_property_value(if is_lateral { "width" } else { "height" }).ok()?.parse().ok()?;
+          (!matches!(style_len, StyleLength::Null)).then_some((p, style_len))
+        }).collect();
+        emit(&emitting, || signal.update(|x| x.extend(map)));
+      }
+    };
 
-    let cb_mousemove = Box::into_raw(Box::new(cb_mousemove));
+    // history sync closure: re-emit and save parcels' lengths
+    let cb_sync = {
+      let (emitting, persist) = (emitting.clone(), persist.clone());
+      move || -> HistorySync {
+        let (emitting, persist) = (emitting.clone(), persist.clone());
+        Rc::new(move || {
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+
+          let Some(parcel_name) = parcel_name else { return };
+          let lengths = collect_parcel_lengths(resizer_rf, is_lateral, to_pixel, parcel_name);
+          if let Some(signal) = parcel_lengths {
+            let map: HashMap<P, StyleLength> = lengths.iter().filter_map(|(name, style_len)| Some((name.parse().ok()?, *style_len))).collect();
+            emit(&emitting, || signal.update(|x| x.extend(map)));
+          }
+          if let Some((persist, _)) = &persist {
+            persist.save_parcels(lengths);
+          }
+        })
+      }
+    };
+
+    // end closure (pointerup & keydown)
+    let cb_end = move || {
+      if resized.replace(false) {
+        if let Some((persist, parcel_name)) = &persist {
+          persist.save_parcels(collect_parcel_lengths(resizer_rf, is_lateral, to_pixel, parcel_name));
+        }
+      }
+    };
+
+    let lock = DragLock::new(drag_cursor.unwrap_or(DragLock::resize_cursor(is_lateral)), shield);
 
     // set each event handlers
-    Self::set_event_handlers(resizer_rf, change_class_on_move, cb_mousemove, moving);
+    Self::set_event_handlers(
+      HandlerSettings { resizer_rf, is_lateral, to_left, min_len, max_len, key_step, change_class_on_move, moving, clamp, clamped, tracker, history, lock, listeners: Rc::downgrade(&listeners) },
+      HandlerCallbacks { step: cb_step, end: cb_end, rollback: cb_rollback, sync: cb_sync },
+    );
+
+    ResizerHandle(listeners)
   }
 
 
-  /// Retreive struct fields and each type's cb_mousemove, then handle other common parts.
+  /// Retreive struct fields and each type's cb_step & cb_end, then handle other common parts.
+  /// * cb_step returns if the length changed.
+  /// * cb_rollback keeps and re-emits the signals' state for Escape. (Element styles are restored here.)
+  /// * cb_sync keeps the state for history, and returns the closure to restore it after undo/redo.
   /// 
-  fn set_event_handlers(resizer_rf: NodeRef<G>, change_class_on_move: Option<(Option<&'static str>, &'static str)>, cb_mousemove: *mut Closure<dyn FnMut(MouseEvent)>, moving: Option<Signal<bool>>) {
+  fn set_event_handlers<F, E, R, S>(settings: HandlerSettings<G>, callbacks: HandlerCallbacks<F, E, R, S>)
+  where F: Fn(Step) -> bool + 'static, E: Fn() + 'static, R: Fn(Rollback) + 'static, S: Fn() -> HistorySync + 'static
+  {
+    let HandlerSettings { resizer_rf, is_lateral, to_left, min_len, max_len, key_step, change_class_on_move, moving, clamp, clamped, tracker, history, lock, listeners } = settings;
+    let HandlerCallbacks { step: cb_step, end: cb_end, rollback: cb_rollback, sync: cb_sync } = callbacks;
+    let (cb_step, cb_end, cb_sync, tracker) = (Rc::new(cb_step), Rc::new(cb_end), Rc::new(cb_sync), Rc::new(tracker));
+    let lock = Rc::new(lock);
+
+    // update clamped signal with clamp state, if changed
+    let clamp_mode = clamp.is_some();
+    let sync_clamped = Rc::new(move |b: Option<bool>| {
+      let (Some(clamp), Some(signal)) = (&clamp, clamped) else { return };
+      let b = b.unwrap_or(clamp.get());
+      clamp.set(b);
+      if signal.get_untracked() != b {
+        signal.set(b);
+      }
+    });
 
     // Must be inside the on_mount scope
     on_mount(move || {
 
-      // mouseup
-      let cb_mouseup: *mut Closure<dyn FnMut(MouseEvent)> = Box::into_raw(Box::new(Closure::<dyn FnMut(_)>::new(move |_: web_sys::MouseEvent| {})));
+      let Some(listeners) = listeners.upgrade() else { return };
+      let Some(target) = dom_node(resizer_rf).map(|x| x.unchecked_into::<HtmlElement>()) else { return };
+
+      // init aria values
+      set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+
+      // keep touch dragging from being taken over by browser's panning
+      target.style().set_property("touch-action", "none").unwrap_throw();
+
+      // id of the pointer which is dragging the resizer
+      let active: Rc<Cell<Option<i32>>> = Rc::new(Cell::new(None));
+
+      // gap between the pointer and the element's edge at drag start (clamp mode)
+      let offset: Rc<Cell<f64>> = Rc::new(Cell::new(0.));
+
+      // inline lengths of elements in the wrap at drag start, to be restored on Escape
+      let snapshot: Rc<RefCell<Vec<(HtmlElement, String)>>> = Rc::new(RefCell::new(Vec::new()));
+      let cb_rollback = Rc::new(cb_rollback);
+
+      // state at drag start, to be restored on undo
+      let sync_before: Rc<RefCell<Option<HistorySync>>> = Rc::new(RefCell::new(None));
+
+      // pointerdown
+      let cb_pointerdown = Closure::<dyn FnMut(_)>::new({
+        let (active, target, offset, tracker) = (active.clone(), target.clone(), offset.clone(), tracker.clone());
+        let (snapshot, cb_rollback, lock) = (snapshot.clone(), cb_rollback.clone(), lock.clone());
+        let (history, cb_sync, sync_before) = (history.clone(), cb_sync.clone(), sync_before.clone());
+        move |e: PointerEvent| {
+          // ignore other pointers while dragging, and non-primary buttons
+          if active.get().is_some() || e.button()!=0 { return; }
+
+          active.set(Some(e.pointer_id()));
+          let _ = target.set_pointer_capture(e.pointer_id());
+
+          // keep the offset from the pointer
+          offset.set(0.);
+          if clamp_mode {
+            if let Some((_, _, gap)) = resizer_parent_element(resizer_rf).and_then(|element| get_lengths(&element, Step::Pointer(MouseEvent::from(e.clone()), 0.), is_lateral, to_left)) {
+              offset.set(gap);
+            }
+          }
+
+          // keep lengths to roll back
+          *snapshot.borrow_mut() = inline_lengths(resizer_rf, is_lateral);
+          cb_rollback(Rollback::Snapshot);
+          if history.is_some() {
+            *sync_before.borrow_mut() = Some(cb_sync());
+          }
+
+          // convert class & set moving true
+          if let Some((old, new)) = change_class_on_move {
+            ChangeClass::replace(resizer_rf, old, new, true);
+          }
+          moving.map(|x| x.set(true));
+          lock.lock();
+          tracker.start();
+        }
+      });
+
+      // pointermove
+      let cb_move = Closure::<dyn FnMut(_)>::new({
+        let (active, cb_step, sync_clamped, tracker) = (active.clone(), cb_step.clone(), sync_clamped.clone(), tracker.clone());
+        move |e: PointerEvent| {
+          if active.get() != Some(e.pointer_id()) { return; }
+          if cb_step(Step::Pointer(e.into(), offset.get())) {
+            tracker.step();
+          }
+          sync_clamped(None);
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+        }
+      });
+
+      // end of drag
+      let end_drag = Rc::new({
+        let (active, target, cb_end, sync_clamped, tracker, snapshot, history) = (active.clone(), target.clone(), cb_end.clone(), sync_clamped.clone(), tracker.clone(), snapshot.clone(), history.clone());
+        let cb_sync = cb_sync.clone();
+        move || {
+          let Some(pointer_id) = active.take() else { return };
+
+          // record the change
+          let before = snapshot.take();
+          let sync_before = sync_before.take();
+          if let (Some(history), Some(sync_before)) = (&history, sync_before) {
+            history.record(is_lateral, &before, &inline_lengths(resizer_rf, is_lateral), (sync_before, cb_sync()));
+          }
+          if target.has_pointer_capture(pointer_id) {
+            let _ = target.release_pointer_capture(pointer_id);
+          }
 
-      unsafe {
-        *cb_mouseup = Closure::<dyn FnMut(_)>::new(move |_: web_sys::MouseEvent| {
-          
           // convert class & set moving false
           if let Some((old, new)) = change_class_on_move {
             ChangeClass::replace(resizer_rf, old, new, false);
           }
           moving.map(|x| x.set(false));
-          
-          let document = gloo_utils::document();
-          document.remove_event_listener_with_callback("mousemove", (*cb_mousemove).as_ref().unchecked_ref()).unwrap_throw();
-          document.remove_event_listener_with_callback("mouseup", (*cb_mouseup).as_ref().unchecked_ref()).unwrap_throw();
-        });
-      }
-      
-      // mousedown
-      let cb_mousedown = Closure::<dyn FnMut(_)>::new(move |_: MouseEvent| {
-        unsafe {
-          let document = gloo_utils::document();
-          document.add_event_listener_with_callback("mousemove", (*cb_mousemove).as_ref().unchecked_ref()).unwrap_throw();
-          document.add_event_listener_with_callback("mouseup", (*cb_mouseup).as_ref().unchecked_ref()).unwrap_throw();
+          lock.unlock();
+          sync_clamped(Some(false));
+          cb_end();
+          tracker.end();
         }
-        // convert class & set moving true
-        if let Some((old, new)) = change_class_on_move {
-          ChangeClass::replace(resizer_rf, old, new, true);
+      });
+
+      // pointerup, pointercancel and lostpointercapture
+      let cb_pointerup = Rc::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: PointerEvent| {
+          if active.get() != Some(e.pointer_id()) { return; }
+          end_drag();
         }
-        moving.map(|x| x.set(true));
       });
 
-      // set mousedown handler
-      let target = resizer_rf.get::<DomNode>().unchecked_into::<EventTarget>();
-      target.add_event_listener_with_callback("mousedown", cb_mousedown.as_ref().unchecked_ref()).unwrap_throw();
-      
-      // consume raw pointers on clean-up
-      on_cleanup(move || {
-        target.remove_event_listener_with_callback("mousedown", cb_mousedown.as_ref().unchecked_ref()).unwrap_throw();
-        unsafe {
-          let _ = Box::from_raw(cb_mousemove);
-          let _ = Box::from_raw(cb_mouseup);
+      // Escape cancels the ongoing drag: restore lengths, re-emit them and end the drag
+      let cb_escape = Closure::<dyn FnMut(_)>::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: KeyboardEvent| {
+          if e.key()!="Escape" || active.get().is_none() { return; }
+          e.prevent_default();
+
+          let property = if is_lateral { "width" } else { "height" };
+          for (element, value) in snapshot.borrow().iter() {
+            if value.is_empty() {
+              let _ = element.style().remove_property(property);
+            } else {
+              element.style().set_property(property, value).unwrap_throw();
+            }
+          }
+          cb_rollback(Rollback::Restore);
+          end_drag();
+          set_aria_values(resizer_rf, is_lateral, min_len, max_len);
         }
       });
+
+      // detaching ends the ongoing drag
+      listeners.on_detach(move || end_drag());
+
+      // keydown
+      let cb_keydown = Closure::<dyn FnMut(_)>::new(move |e: KeyboardEvent| {
+        let Some(element) = resizer_parent_element(resizer_rf) else { return };
+        let Some(step) = key_to_step(&e, &element, is_lateral, to_left, min_len, max_len, key_step) else { return };
+
+        e.prevent_default();
+        tracker.start();
+        let before = history.as_ref().map(|_| (inline_lengths(resizer_rf, is_lateral), cb_sync()));
+        if cb_step(step) {
+          tracker.step();
+        }
+        if let (Some(history), Some((before, sync_before))) = (&history, before) {
+          history.record(is_lateral, &before, &inline_lengths(resizer_rf, is_lateral), (sync_before, cb_sync()));
+        }
+        sync_clamped(Some(false));
+        cb_end();
+        tracker.end();
+        set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+      });
+
+      // focus: other resizers in the same wrap might have changed the length
+      let cb_focus = Closure::<dyn FnMut(_)>::new(move |_: Event| {
+        set_aria_values(resizer_rf, is_lateral, min_len, max_len);
+      });
+
+      // set handlers (removed on clean-up, or by the handle)
+      let target: &EventTarget = &target;
+      listeners.listen(target, "keydown", cb_keydown, false);
+      listeners.listen(target, "focus", cb_focus, false);
+      listeners.listen(target, "pointerdown", cb_pointerdown, false);
+      listeners.listen(target, "pointermove", cb_move, false);
+      listeners.listen(&gloo_utils::document(), "keydown", cb_escape, false);
+      for event in POINTER_END_EVENTS {
+        listeners.listen(target, event, Closure::<dyn FnMut(_)>::new({
+          let cb_pointerup = cb_pointerup.clone();
+          move |e: PointerEvent| cb_pointerup(e)
+        }), false);
+      }
     });
   }
 }
 
 
+/// Events which end a pointer drag
+/// 
+pub(crate) const POINTER_END_EVENTS: [&str; 3] = ["pointerup", "pointercancel", "lostpointercapture"];
+
+/// Default (step, step with Shift key) of keyboard resizing
+/// 
+const KEY_STEP: (StyleLength, StyleLength) = (StyleLength::Pixel(10.), StyleLength::Pixel(50.));
+
+/// Rollback of a drag
+/// * Snapshot: a drag started. Keep the state to restore.
+/// * Restore: the drag is canceled (Escape). Restore the state and re-emit it.
+/// 
+pub(crate) enum Rollback {
+  Snapshot,
+  Restore,
+}
+
+/// Settings of the event handlers, common to both types
+/// * clamp: clamp state (clamp mode only), and clamped: its signal.
+/// * listeners: shared with the handle. Handlers are added to them on mount.
+/// 
+struct HandlerSettings<G: GenericNode> {
+  resizer_rf: NodeRef<G>,
+  is_lateral: bool,
+  to_left: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  key_step: Option<(StyleLength, StyleLength)>,
+  change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+  moving: Option<Signal<bool>>,
+  clamp: Option<Rc<Cell<bool>>>,
+  clamped: Option<Signal<bool>>,
+  tracker: ResizeTracker<G>,
+  history: Option<LayoutHistory>,
+  lock: DragLock,
+  listeners: std::rc::Weak<Listeners>,
+}
+
+/// Each type's closures for the event handlers (Look at `set_event_handlers`)
+/// 
+struct HandlerCallbacks<F, E, R, S> {
+  step: F,
+  end: E,
+  rollback: R,
+  sync: S,
+}
+
+/// What moves the resizer
+/// * Pointer: gap between pointer and the element's edge, less the offset kept from drag start
+/// * Gap: given gap(px) (e.g. from keyboard)
+/// * To: exact length(px) of the element, already between its limitations (e.g. Home/End keys)
+/// 
+pub(crate) enum Step {
+  Pointer(MouseEvent, f64),
+  Gap(f64),
+  To(f64),
+}
+
+
 // helpers
 
 /// resizer's parent element
 fn resizer_parent_element<G: GenericNode>(resizer_rf: NodeRef<G>) -> Option<Element> {
-  resizer_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<Element>()).map(|x| x.parent_element()).flatten()
+  dom_node(resizer_rf).map(|x| x.unchecked_into::<Element>()).map(|x| x.parent_element()).flatten()
 }
 
 /// Element's Scroll length
 /// * Use this to get wrapping element's length 
 /// 
-fn scroll_length(element: &Element, is_lateral: bool) -> f64 {
+pub(crate) fn scroll_length(element: &Element, is_lateral: bool) -> f64 {
 
   let v = if is_lateral {
     element.scroll_width()
@@ -271,7 +960,7 @@ fn get_length(element: &Element, is_lateral: bool) -> f64 {
 /// 
 fn get_lengths(
   element: &Element,
-  e: MouseEvent,
+  step: Step,
   is_lateral: bool, 
   to_left: bool
 ) -> Option<(f64, f64, f64)> {
@@ -280,15 +969,241 @@ fn get_lengths(
   let Some(parent) = element.parent_element() else { return None };
   let par_len = scroll_length(&parent, is_lateral);
 
+  let (e, offset) = match step {
+    Step::Pointer(e, offset) => (e, offset),
+    Step::Gap(gap) => return Some((par_len, get_length(element, is_lateral), gap)),
+    Step::To(to_len) => {
+      let len = get_length(element, is_lateral);
+      return Some((par_len, len, to_len-len));
+    },
+  };
+
   let rect = element.get_bounding_client_rect();
 
   Some(if is_lateral {
     let cur = e.client_x() as f64;
     let gap = if to_left { rect.left()-cur } else { cur-rect.right() };
-    (par_len, rect.width(), gap)
+    (par_len, rect.width(), gap-offset)
   } else {
     let cur = e.client_y() as f64;
     let gap = if to_left { rect.top()-cur } else { cur-rect.bottom() };
-    (par_len, rect.height(), gap)
+    (par_len, rect.height(), gap-offset)
   })
+}
+
+
+/// Inline lengths(width/height) of elements in the wrap: resizer's panel/parcel and its siblings
+/// 
+fn inline_lengths<G: GenericNode>(resizer_rf: NodeRef<G>, is_lateral: bool) -> Vec<(HtmlElement, String)> {
+  wrap_parcels(resizer_rf).into_iter().map(|element| {
+    let element = element.unchecked_into::<HtmlElement>();
+    let value = element.style().get_property_value(if is_lateral { "width" } else { "height" }).unwrap_or_default();
+    (element, value)
+  }).collect()
+}
+
+/// Length of element: its inline length, or the measured one if it's not in StyleLength's format (e.g. `calc(...)`)
+/// 
+fn current_length(element: &Element, is_lateral: bool, to_pixel: bool) -> Option<StyleLength> {
+  let value = element.unchecked_ref::<HtmlElement>().style().get_property_value(if is_lateral { "width" } else { "height" }).ok()?;
+  match value.parse::<StyleLength>() {
+    Ok(StyleLength::Null) | Err(_) => StyleLength::from_rect(element, is_lateral, to_pixel),
+    Ok(style_len) => Some(style_len),
+  }
+}
+
+/// Update signal as the resizer's own update
+/// * In controlled mode, it is not applied back to the DOM.
+/// 
+fn emit(emitting: &Cell<bool>, f: impl FnOnce()) {
+  emitting.set(true);
+  f();
+  emitting.set(false);
+}
+
+
+/// Update collapsed signal with collapse state, if changed
+/// 
+fn sync_collapsed(collapsed: Option<Signal<bool>>, collapse: Option<&Collapse>) {
+  if let (Some(signal), Some(collapse)) = (collapsed, collapse) {
+    let b = collapse.collapsed.get();
+    if signal.get_untracked() != b {
+      signal.set(b);
+    }
+  }
+}
+
+/// Limitation check of a length (panel type, and each axis of corner type)
+/// * (1) between [0 ~ wrapping element's length]
+/// * (2) expanding: max limitation, shrinking: min limitation
+/// 
+fn length_check(len: f64, to_len: f64, par_len: f64, min_len: Option<StyleLength>, max_len: Option<StyleLength>) -> bool {
+  if to_len<0. || par_len<to_len { return false; }
+
+  if to_len>len {
+    max_len.map(|x| x.max_check(to_len, par_len)).unwrap_or(true)
+  } else {
+    min_len.map(|x| x.min_check(to_len, par_len)).unwrap_or(true)
+  }
+}
+
+/// Limitations of the element
+/// * its own `data-min-len`/`data-max-len` attributes, or given ones.
+/// 
+fn element_limits(element: &Element, min_len: Option<StyleLength>, max_len: Option<StyleLength>) -> (Option<StyleLength>, Option<StyleLength>) {
+  let dataset = element.unchecked_ref::<HtmlElement>().dataset();
+  let get = |name: &str| dataset.get(name)
+    .and_then(|x| x.parse::<StyleLength>().ok())
+    .filter(|x| !matches!(x, StyleLength::Null));
+
+  (get("minLen").or(min_len), get("maxLen").or(max_len))
+}
+
+/// Length(px) clamped between min/max limitations(and wrapping element's length)
+/// * Return None if it's already between them. (within `CLAMP_EPSILON`)
+/// * If the limitations conflict, min limitation wins.
+/// 
+fn clamp_length(len: f64, par_len: f64, min_len: Option<StyleLength>, max_len: Option<StyleLength>) -> Option<f64> {
+  let to_len = len.min(max_bound(max_len, par_len)).max(min_bound(min_len, par_len));
+  ((to_len-len).abs()>CLAMP_EPSILON).then_some(to_len)
+}
+
+/// Tolerance(px) of responsive clamping, absorbing rounding of style values
+const CLAMP_EPSILON: f64 = 0.5;
+
+/// Observe size of the wrapping element(parent of resizer's parent), and call f on change
+/// * Must be inside the on_mount scope. f is removed on clean-up.
+/// * Resizers in the same wrap share one observer (Look at `WRAP_OBSERVERS`), which calls their f in order.
+/// 
+fn observe_wrap<G: GenericNode>(resizer_rf: NodeRef<G>, f: impl Fn() + 'static) {
+  let Some(wrap) = resizer_parent_element(resizer_rf).and_then(|x| x.parent_element()) else { return };
+  let f: Rc<dyn Fn()> = Rc::new(f);
+
+  WRAP_OBSERVERS.with(|observers| {
+    let mut observers = observers.borrow_mut();
+    if let Some(observer) = observers.iter().find(|x| x.wrap==wrap) {
+      observer.callbacks.borrow_mut().push(f.clone());
+      return;
+    }
+
+    let callbacks = Rc::new(RefCell::new(vec![f.clone()]));
+    let cb = {
+      let callbacks = callbacks.clone();
+      Closure::<dyn FnMut(_)>::new(move |_: JsValue| {
+        // callbacks may be removed while called
+        let callbacks = callbacks.borrow().clone();
+        callbacks.iter().for_each(|f| f());
+      })
+    };
+    let observer = ResizeObserver::new(cb.as_ref().unchecked_ref()).unwrap_throw();
+    observer.observe(&wrap);
+    observers.push(WrapObserver { wrap: wrap.clone(), observer, callbacks, _cb: cb });
+  });
+
+  on_cleanup(move || {
+    WRAP_OBSERVERS.with(|observers| {
+      let mut observers = observers.borrow_mut();
+      let Some(i) = observers.iter().position(|x| x.wrap==wrap) else { return };
+      observers[i].callbacks.borrow_mut().retain(|x| !Rc::ptr_eq(x, &f));
+      if observers[i].callbacks.borrow().is_empty() {
+        observers.remove(i).observer.disconnect();
+      }
+    });
+  });
+}
+
+/// ResizeObserver of a wrap, shared by responsive resizers in it
+/// 
+struct WrapObserver {
+  wrap: Element,
+  observer: ResizeObserver,
+  callbacks: Rc<RefCell<Vec<Rc<dyn Fn()>>>>,
+  _cb: Closure<dyn FnMut(JsValue)>,
+}
+
+thread_local! {
+  /// Observed wraps
+  /// * Every resizer between parcels of a wrap is responsive at once (e.g. `SplitLayout`),
+  ///   and one observer per wrap keeps the wrap from being clamped over and over.
+  static WRAP_OBSERVERS: RefCell<Vec<WrapObserver>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Smallest length(px) allowed by min limitation
+/// 
+fn min_bound(min_len: Option<StyleLength>, par_len: f64) -> f64 {
+  match min_len {
+    Some(StyleLength::Pixel(v)) => v,
+    Some(StyleLength::Percent(v)) => v*par_len/100.,
+    Some(StyleLength::PixelPercent(v1, v2)) => v1.max(v2*par_len/100.),
+    Some(x) => x.to_pixel(par_len),
+    None => 0.,
+  }
+}
+
+/// Largest length(px) allowed by max limitation and wrapping element's length
+/// 
+fn max_bound(max_len: Option<StyleLength>, par_len: f64) -> f64 {
+  match max_len {
+    Some(StyleLength::Pixel(v)) => v.min(par_len),
+    Some(StyleLength::Percent(v)) => (v*par_len/100.).min(par_len),
+    Some(StyleLength::PixelPercent(v1, v2)) => v1.min(v2*par_len/100.).min(par_len),
+    Some(StyleLength::Null) | None => par_len,
+    Some(x) => x.to_pixel(par_len).min(par_len),
+  }
+}
+
+/// Set aria-valuenow/min/max of resizer element, in percent of the wrapping element's length.
+/// 
+fn set_aria_values<G: GenericNode>(resizer_rf: NodeRef<G>, is_lateral: bool, min_len: Option<StyleLength>, max_len: Option<StyleLength>) {
+
+  let Some(resizer) = dom_node(resizer_rf).map(|x| x.unchecked_into::<Element>()) else { return };
+  let Some(element) = resizer.parent_element() else { return };
+  let Some(parent) = element.parent_element() else { return };
+
+  let par_len = scroll_length(&parent, is_lateral);
+  if par_len<=0. { return; }
+  let len = get_length(&element, is_lateral);
+  let (min_len, max_len) = element_limits(&element, min_len, max_len);
+
+  for (name, v) in [
+    ("aria-valuenow", len),
+    ("aria-valuemin", min_bound(min_len, par_len)),
+    ("aria-valuemax", max_bound(max_len, par_len)),
+  ] {
+    resizer.set_attribute(name, &format!("{:.2}", v/par_len*100.)).unwrap_throw();
+  }
+}
+
+/// Convert keydown into step of the element
+/// * Arrow keys: gap(px) by key_step (larger step with Shift key)
+/// * Home/End: exact length of the min/max limitation
+/// 
+fn key_to_step(
+  e: &KeyboardEvent,
+  element: &Element,
+  is_lateral: bool,
+  to_left: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  key_step: Option<(StyleLength, StyleLength)>
+) -> Option<Step> {
+
+  let Some(parent) = element.parent_element() else { return None };
+  let par_len = scroll_length(&parent, is_lateral);
+  let (min_len, max_len) = element_limits(element, min_len, max_len);
+
+  let (step, step_shift) = key_step.unwrap_or(KEY_STEP);
+  let step = if e.shift_key() { step_shift } else { step }.to_pixel(par_len);
+
+  // movement toward right/bottom is positive
+  let delta = match (e.key().as_str(), is_lateral) {
+    ("ArrowLeft", true) | ("ArrowUp", false) => -step,
+    ("ArrowRight", true) | ("ArrowDown", false) => step,
+    ("Home", _) => return Some(Step::To(min_bound(min_len, par_len))),
+    ("End", _) => return Some(Step::To(max_bound(max_len, par_len))),
+    _ => return None,
+  };
+
+  // resizer at the left/top side expands the element when moving toward left/top
+  Some(Step::Gap(if to_left { -delta } else { delta }))
 }
\ No newline at end of file
@@ -9,7 +9,9 @@ use crate::*;
 #[derive(Props)]
 pub struct PanelResizerProps{
   moving: Option<Signal<bool>>,
+  clamped: Option<Signal<bool>>,
   panel_length: Option<Signal<StyleLength>>,
+  collapsed: Option<Signal<bool>>,
   class: &'static str,
   is_lateral: bool,
   to_left: bool,
@@ -18,6 +20,20 @@ pub struct PanelResizerProps{
   max_len: Option<StyleLength>,
   change_class_on_move: Option<(Option<&'static str>, &'static str)>,
   skip_set_style: Option<bool>,
+  key_step: Option<(StyleLength, StyleLength)>,
+  collapse_threshold: Option<StyleLength>,
+  collapse_rail: Option<StyleLength>,
+  persist: Option<Persist>,
+  controlled: Option<bool>,
+  enabled: Option<ReadSignal<bool>>,
+  clamp: Option<bool>,
+  on_resize_start: Option<ResizeCallback>,
+  on_resize: Option<ResizeCallback>,
+  on_resize_end: Option<ResizeCallback>,
+  history: Option<LayoutHistory>,
+  responsive: Option<bool>,
+  drag_cursor: Option<&'static str>,
+  shield: Option<bool>,
 }
 
 /// PanelResizer
@@ -26,7 +42,9 @@ pub struct PanelResizerProps{
 /// 
 /// # Props
 /// * moving: Option<Signal<bool>>,
+/// * clamped: Option<Signal<bool>>,
 /// * panel_length: Option<Signal<StyleLength>>,
+/// * collapsed: Option<Signal<bool>>,
 /// * class: &'static str,
 /// * is_lateral: bool,
 /// * to_left: bool,
@@ -35,6 +53,22 @@ pub struct PanelResizerProps{
 /// * max_len: Option<StyleLength>,
 /// * change_class_on_move: Option<(Option<&'static str>, &'static str)>,
 /// * skip_set_style: Option<bool>,
+/// * key_step: Option<(StyleLength, StyleLength)>,
+/// * collapse_threshold: Option<StyleLength>,
+/// * collapse_rail: Option<StyleLength>,
+/// * persist: Option<Persist>,
+/// * controlled: Option<bool>,
+/// * enabled: Option<ReadSignal<bool>>,
+/// * clamp: Option<bool>,
+/// * on_resize_start: Option<ResizeCallback>,
+/// * on_resize: Option<ResizeCallback>,
+/// * on_resize_end: Option<ResizeCallback>,
+/// * history: Option<LayoutHistory>,
+/// * responsive: Option<bool>,
+/// * drag_cursor: Option<&'static str>,
+/// * shield: Option<bool>,
+/// 
+/// The resizer element is rendered as a focusable separator (role, tabindex and aria-orientation), with {"touch-action: none"} inline.
 /// 
 #[component]
 pub fn PanelResizer<G: Html>(props: PanelResizerProps) -> View<G> {
@@ -49,10 +83,30 @@ pub fn PanelResizer<G: Html>(props: PanelResizerProps) -> View<G> {
     max_len: props.max_len,
     change_class_on_move: props.change_class_on_move,
     resizer_rf: rf,
+    key_step: props.key_step,
+    collapse_threshold: props.collapse_threshold,
+    collapse_rail: props.collapse_rail,
+    collapsed: props.collapsed,
+    shrink_strategy: ShrinkStrategy::default(),
+    persist: props.persist,
+    controlled: props.controlled.unwrap_or(false),
+    enabled: props.enabled,
+    clamp: props.clamp.unwrap_or(false),
+    clamped: props.clamped,
+    on_resize_start: props.on_resize_start,
+    on_resize: props.on_resize,
+    on_resize_end: props.on_resize_end,
+    history: props.history,
+    responsive: props.responsive.unwrap_or(false),
+    drag_cursor: props.drag_cursor,
+    shield: props.shield.unwrap_or(false),
   }.set_panel_resizer(props.moving, props.panel_length, props.skip_set_style.unwrap_or(false));
 
   view! {
-    div(ref=rf, class=props.class)
+    div(ref=rf, class=props.class,
+      role="separator", tabindex="0", style="touch-action: none;",
+      aria-orientation=if props.is_lateral { "vertical" } else { "horizontal" },
+    )
   }
 }
 
@@ -67,6 +121,7 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr + 'static
 {
   rf: Option<NodeRef<G>>,
   moving: Option<Signal<bool>>,
+  clamped: Option<Signal<bool>>,
   parcel_lengths: Option<Signal<HashMap<P, StyleLength>>>,
   parcel_name: Option<&'static str>,
   class: &'static str,
@@ -77,16 +132,31 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr + 'static
   max_len: Option<StyleLength>,
   change_class_on_move: Option<(Option<&'static str>, &'static str)>,
   skip_set_style: Option<bool>,
+  key_step: Option<(StyleLength, StyleLength)>,
+  shrink_strategy: Option<ShrinkStrategy>,
+  persist: Option<Persist>,
+  controlled: Option<bool>,
+  enabled: Option<ReadSignal<bool>>,
+  clamp: Option<bool>,
+  on_resize_start: Option<ResizeCallback>,
+  on_resize: Option<ResizeCallback>,
+  on_resize_end: Option<ResizeCallback>,
+  history: Option<LayoutHistory>,
+  responsive: Option<bool>,
+  drag_cursor: Option<&'static str>,
+  shield: Option<bool>,
 }
 
 
 /// ParcelsResizer
 /// * Parcels type
 /// * Look at `Resizer`'s `set_parcels_resizer` for more information.
+/// * min_len/max_len apply to every parcel in the wrap, unless a parcel has its own `data-min-len`/`data-max-len`.
 /// 
 /// # Props
 /// * rf: Option<NodeRef<G>>,
 /// * moving: Option<Signal<bool>>,
+/// * clamped: Option<Signal<bool>>,
 /// * parcel_lengths: Option<Signal<HashMap<P, StyleLength>>>,
 /// * parcel_name: Option<&'static str>,
 /// * class: &'static str,
@@ -97,6 +167,21 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr + 'static
 /// * max_len: Option<StyleLength>,
 /// * change_class_on_move: Option<(Option<&'static str>, &'static str)>,
 /// * skip_set_style: Option<bool>,
+/// * key_step: Option<(StyleLength, StyleLength)>,
+/// * shrink_strategy: Option<ShrinkStrategy>,
+/// * persist: Option<Persist>,
+/// * controlled: Option<bool>,
+/// * enabled: Option<ReadSignal<bool>>,
+/// * clamp: Option<bool>,
+/// * on_resize_start: Option<ResizeCallback>,
+/// * on_resize: Option<ResizeCallback>,
+/// * on_resize_end: Option<ResizeCallback>,
+/// * history: Option<LayoutHistory>,
+/// * responsive: Option<bool>,
+/// * drag_cursor: Option<&'static str>,
+/// * shield: Option<bool>,
+/// 
+/// The resizer element is rendered as a focusable separator (role, tabindex and aria-orientation), with {"touch-action: none"} inline.
 /// 
 #[component]
 pub fn ParcelsResizer<G: Html, P>(props: ParcelsResizerProps<G, P>) -> View<G>
@@ -111,10 +196,30 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr
     min_len: props.min_len,
     max_len: props.max_len,
     change_class_on_move: props.change_class_on_move,
-    resizer_rf: rf
+    resizer_rf: rf,
+    key_step: props.key_step,
+    collapse_threshold: None,
+    collapse_rail: None,
+    collapsed: None,
+    shrink_strategy: props.shrink_strategy.unwrap_or_default(),
+    persist: props.persist,
+    controlled: props.controlled.unwrap_or(false),
+    enabled: props.enabled,
+    clamp: props.clamp.unwrap_or(false),
+    clamped: props.clamped,
+    on_resize_start: props.on_resize_start,
+    on_resize: props.on_resize,
+    on_resize_end: props.on_resize_end,
+    history: props.history,
+    responsive: props.responsive.unwrap_or(false),
+    drag_cursor: props.drag_cursor,
+    shield: props.shield.unwrap_or(false),
   }.set_parcels_resizer(props.moving, props.parcel_lengths, props.parcel_name, props.skip_set_style.unwrap_or(false));
 
   view! {
-    div(ref=rf, class=props.class)
+    div(ref=rf, class=props.class,
+      role="separator", tabindex="0", style="touch-action: none;",
+      aria-orientation=if props.is_lateral { "vertical" } else { "horizontal" },
+    )
   }
 }
\ No newline at end of file
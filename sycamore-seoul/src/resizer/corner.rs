@@ -0,0 +1,478 @@
+use super::*;
+
+
+/// Corner resizer's event handler setter
+/// 
+/// # Description
+/// * Two-dimensional version of panel-type `Resizer`: the resizer (grip) changes both width and height of its parent element.
+/// * Floating panels or preview boxes would have it at a corner.
+/// 
+///   ```
+///     /* example structure*/
+///     // wrap { position: relative; }
+///     //   box { position: absolute; }
+///     //     corner-resizer { position: absolute; right: 0; bottom: 0; }
+///   ```
+/// 
+/// # Use
+/// Build the struct in raw format or via method `new()`, and then call `set_corner_resizer`.
+/// * It returns `ResizerHandle`, the same as `Resizer`. (Look at `ResizerHandle`)
+/// * `CornerResizerComponent` is a Sycamore native component wrapping all the logics.
+/// 
+/// # Fields
+/// * to_left(bool): is the grip at the left side of the element? (Otherwise right side)
+/// * to_top(bool): is the grip at the top side of the element? (Otherwise bottom side)
+/// * to_pixel(bool): set style in pixel or percent?
+/// * min_len: (width, height) min limitations
+/// * max_len: (width, height) max limitations
+///   * The element's own `data-min-len`/`data-max-len` override them on both axes, the same as `Resizer`'s.
+/// * lock_aspect(bool): keep the aspect ratio (width:height) of when the drag started.
+/// * change_class_on_move: on moving, convert class from old to new, and vice versa on stop. (Look at `ChangeClass`)
+/// * resizer_rf: the NodeRef of actual resizer element.
+/// * key_step: (step, step with Shift key) of keyboard resizing. Default is (10px, 50px).
+/// * enabled: switch the resizer's event listeners on and off reactively.
+/// * drag_cursor: cursor of the whole document during a drag. Default is `nwse-resize` or `nesw-resize`, by the corner.
+/// * shield: put up a full-viewport transparent overlay during a drag, so iframes under the pointer don't take its events.
+/// * on_resize_start, on_resize, on_resize_end: resize lifecycle callbacks, the same as `Resizer`'s.
+///   * They're called for each axis, and `is_lateral` of `ResizeEvent` tells which. (on_resize only for the axis which changed)
+/// * history: record each completed drag (and keyboard step) for undo/redo, as one change of both axes. (Look at `LayoutHistory`)
+/// 
+/// # How it works
+/// * Pointer and keyboard handling is the same as `Resizer`'s. (Arrow keys resize width or height.)
+/// * Escape during a drag restores the length of when it started.
+/// * Each axis goes through the same limitation check as `Resizer`'s:
+///   * (1) Is it between [0 ~ wrapping element's length]? (2) Is it between min_len and max_len of the axis?
+///   * If an axis fails, only that axis stays. With lock_aspect, nothing changes.
+/// 
+#[derive(Debug, Clone)]
+pub struct CornerResizer<G: GenericNode> {
+  pub to_left: bool,
+  pub to_top: bool,
+  pub to_pixel: bool,
+  pub min_len: (Option<StyleLength>, Option<StyleLength>),
+  pub max_len: (Option<StyleLength>, Option<StyleLength>),
+  pub lock_aspect: bool,
+  pub change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+  pub resizer_rf: NodeRef<G>,
+  pub key_step: Option<(StyleLength, StyleLength)>,
+  pub enabled: Option<ReadSignal<bool>>,
+  pub drag_cursor: Option<&'static str>,
+  pub shield: bool,
+  pub on_resize_start: Option<ResizeCallback>,
+  pub on_resize: Option<ResizeCallback>,
+  pub on_resize_end: Option<ResizeCallback>,
+  pub history: Option<LayoutHistory>,
+}
+
+impl<G: GenericNode> CornerResizer<G> {
+
+  /// Build new struct
+  /// * Other fields are set in default. Change them directly if needed.
+  /// 
+  pub fn new(to_left: bool, to_top: bool, to_pixel: bool, min_len: (Option<StyleLength>, Option<StyleLength>), max_len: (Option<StyleLength>, Option<StyleLength>), resizer_rf: NodeRef<G>) -> Self {
+    Self {
+      to_left, to_top, to_pixel, min_len, max_len, lock_aspect: false, change_class_on_move: None, resizer_rf, key_step: None, enabled: None, drag_cursor: None,
+      shield: false, on_resize_start: None, on_resize: None, on_resize_end: None, history: None,
+    }
+  }
+
+  /// Set corner resizer's event handlers
+  /// 
+  /// # Args (signals)
+  /// * moving: update if is it moving(resizing) or not
+  /// * lengths: update the adjusted (width, height) at every moving step.
+  /// * skip_set_style(bool): If it's true, css property wouldn't be changed: only given signals would be updated.
+  /// 
+  /// # Return
+  /// * ResizerHandle: detaches and reattaches the event listeners.
+  /// 
+  pub fn set_corner_resizer(self,
+    moving: Option<Signal<bool>>,
+    lengths: Option<Signal<(StyleLength, StyleLength)>>,
+    skip_set_style: bool
+  ) -> ResizerHandle {
+
+    // expand self
+    let Self { to_left, to_top, to_pixel, min_len, max_len, lock_aspect, change_class_on_move, resizer_rf, key_step, enabled, drag_cursor, shield, on_resize_start, on_resize, on_resize_end, history } = self;
+
+    // event listeners, kept until clean-up and controlled by the handle
+    let listeners = Listeners::new(enabled);
+    Listeners::keep_until_cleanup(&listeners);
+
+    let corner = Rc::new(Corner { to_left, to_top, to_pixel, min_len, max_len, lock_aspect, skip_set_style, lengths, start: Cell::new(None), offset: Cell::new((0., 0.)) });
+
+    // lifecycle trackers of (width, height)
+    let tracker = |is_lateral: bool| ResizeTracker::new(resizer_rf, is_lateral, to_pixel, None, on_resize_start.clone(), on_resize.clone(), on_resize_end.clone());
+    let trackers = Rc::new((tracker(true), tracker(false)));
+
+    // history sync closure: re-emit lengths signal from the element
+    let cb_sync = move || -> HistorySync {
+      Rc::new(move || {
+        let Some(element) = resizer_parent_element(resizer_rf) else { return };
+        let (Some(len_w), Some(len_h)) = (current_length(&element, true, to_pixel), current_length(&element, false, to_pixel)) else { return };
+        if let Some(signal) = lengths {
+          signal.set((len_w, len_h));
+        }
+      })
+    };
+    // inline lengths of (width, height), and the sync of the moment
+    let snapshot = move || resizer_parent_element(resizer_rf).map(|element| {
+      let element = element.unchecked_into::<HtmlElement>();
+      let value = |property: &str| element.style().get_property_value(property).unwrap_or_default();
+      (vec![(element.clone(), value("width"))], vec![(element.clone(), value("height"))], cb_sync())
+    });
+    // record the change from the snapshot before it
+    let recording = history.is_some();
+    let record = Rc::new(move |before: Option<(Vec<(HtmlElement, String)>, Vec<(HtmlElement, String)>, HistorySync)>| {
+      let (Some(history), Some((before_w, before_h, sync_before)), Some((after_w, after_h, sync_after))) = (&history, before, snapshot()) else { return };
+      history.record_axes(&[(true, &before_w, &after_w), (false, &before_h, &after_h)], (sync_before, sync_after));
+    });
+    let before = Rc::new(RefCell::new(None));
+
+    let weak = Rc::downgrade(&listeners);
+    on_mount(move || {
+
+      let Some(listeners) = weak.upgrade() else { return };
+      let Some(target) = dom_node(resizer_rf).map(|x| x.unchecked_into::<HtmlElement>()) else { return };
+
+      // keep touch dragging from being taken over by browser's panning
+      target.style().set_property("touch-action", "none").unwrap_throw();
+
+      // id of the pointer which is dragging the resizer
+      let active: Rc<Cell<Option<i32>>> = Rc::new(Cell::new(None));
+
+      let lock = Rc::new(DragLock::new(drag_cursor.unwrap_or(if to_left==to_top { "nwse-resize" } else { "nesw-resize" }), shield));
+
+      // pointerdown
+      let cb_pointerdown = Closure::<dyn FnMut(_)>::new({
+        let (active, target, corner, lock, trackers, before) = (active.clone(), target.clone(), corner.clone(), lock.clone(), trackers.clone(), before.clone());
+        move |e: PointerEvent| {
+          // ignore other pointers while dragging, and non-primary buttons
+          if active.get().is_some() || e.button()!=0 { return; }
+          let Some(element) = resizer_parent_element(resizer_rf) else { return };
+
+          active.set(Some(e.pointer_id()));
+          let _ = target.set_pointer_capture(e.pointer_id());
+          corner.start(&element, Some(&e));
+          if recording {
+            *before.borrow_mut() = snapshot();
+          }
+
+          // convert class & set moving true
+          if let Some((old, new)) = change_class_on_move {
+            ChangeClass::replace(resizer_rf, old, new, true);
+          }
+          moving.map(|x| x.set(true));
+          lock.lock();
+          trackers.0.start();
+          trackers.1.start();
+        }
+      });
+
+      // pointermove
+      let cb_move = Closure::<dyn FnMut(_)>::new({
+        let (active, corner, trackers) = (active.clone(), corner.clone(), trackers.clone());
+        move |e: PointerEvent| {
+          if active.get() != Some(e.pointer_id()) { return; }
+          let Some(element) = resizer_parent_element(resizer_rf) else { return };
+          let (changed_w, changed_h) = corner.step(&element, CornerStep::Pointer(e.into()));
+          if changed_w { trackers.0.step(); }
+          if changed_h { trackers.1.step(); }
+        }
+      });
+
+      // end of drag
+      let end_drag = Rc::new({
+        let (active, target, corner, trackers, record) = (active.clone(), target.clone(), corner.clone(), trackers.clone(), record.clone());
+        move || {
+          let Some(pointer_id) = active.take() else { return };
+          if target.has_pointer_capture(pointer_id) {
+            let _ = target.release_pointer_capture(pointer_id);
+          }
+          corner.start.set(None);
+          record(before.take());
+
+          // convert class & set moving false
+          if let Some((old, new)) = change_class_on_move {
+            ChangeClass::replace(resizer_rf, old, new, false);
+          }
+          moving.map(|x| x.set(false));
+          lock.unlock();
+          trackers.0.end();
+          trackers.1.end();
+        }
+      });
+
+      // pointerup, pointercancel and lostpointercapture
+      let cb_pointerup = Rc::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: PointerEvent| {
+          if active.get() != Some(e.pointer_id()) { return; }
+          end_drag();
+        }
+      });
+
+      // Escape cancels the ongoing drag
+      let cb_escape = Closure::<dyn FnMut(_)>::new({
+        let (active, end_drag, corner) = (active.clone(), end_drag.clone(), corner.clone());
+        move |e: KeyboardEvent| {
+          if e.key()!="Escape" || active.get().is_none() { return; }
+          e.prevent_default();
+
+          if let Some(element) = resizer_parent_element(resizer_rf) {
+            corner.restore(&element);
+          }
+          end_drag();
+        }
+      });
+
+      // detaching ends the ongoing drag
+      listeners.on_detach(move || end_drag());
+
+      // keydown: arrows resize width(left/right) or height(up/down)
+      let cb_keydown = Closure::<dyn FnMut(_)>::new(move |e: KeyboardEvent| {
+        let Some(element) = resizer_parent_element(resizer_rf) else { return };
+        let Some(parent) = element.parent_element() else { return };
+
+        let (step, step_shift) = key_step.unwrap_or(KEY_STEP);
+        let step = if e.shift_key() { step_shift } else { step };
+
+        // movement toward right/bottom is positive
+        let (is_lateral, delta) = match e.key().as_str() {
+          "ArrowLeft" => (true, -step.to_pixel(scroll_length(&parent, true))),
+          "ArrowRight" => (true, step.to_pixel(scroll_length(&parent, true))),
+          "ArrowUp" => (false, -step.to_pixel(scroll_length(&parent, false))),
+          "ArrowDown" => (false, step.to_pixel(scroll_length(&parent, false))),
+          _ => return,
+        };
+
+        // grip at the left/top side expands the element when moving toward left/top
+        let to_start = if is_lateral { to_left } else { to_top };
+        let gap = if to_start { -delta } else { delta };
+
+        e.prevent_default();
+        let tracker = if is_lateral { &trackers.0 } else { &trackers.1 };
+        let before = if recording { snapshot() } else { None };
+        tracker.start();
+        corner.start(&element, None);
+        if corner.step(&element, if is_lateral { CornerStep::Gap(gap, 0.) } else { CornerStep::Gap(0., gap) }) != (false, false) {
+          tracker.step();
+        }
+        corner.start.set(None);
+        record(before);
+        tracker.end();
+      });
+
+      // set handlers (removed on clean-up, or by the handle)
+      let target: &EventTarget = &target;
+      listeners.listen(target, "keydown", cb_keydown, false);
+      listeners.listen(target, "pointerdown", cb_pointerdown, false);
+      listeners.listen(target, "pointermove", cb_move, false);
+      listeners.listen(&gloo_utils::document(), "keydown", cb_escape, false);
+      for event in POINTER_END_EVENTS {
+        listeners.listen(target, event, Closure::<dyn FnMut(_)>::new({
+          let cb_pointerup = cb_pointerup.clone();
+          move |e: PointerEvent| cb_pointerup(e)
+        }), false);
+      }
+    });
+
+    ResizerHandle(listeners)
+  }
+}
+
+
+/// What moves the corner resizer
+/// * Pointer: pointer's position, on both axes
+/// * Gap: given gaps(px) of (width, height) (e.g. from keyboard)
+/// 
+enum CornerStep {
+  Pointer(MouseEvent),
+  Gap(f64, f64),
+}
+
+
+/// Corner resizing state
+/// * start: (width, height) of when the resize started
+/// * offset: gaps(px) of (width, height) between the pointer and the element's edges at drag start.
+///   The grip sits inside the element, so following steps keep them not to jump to the pointer.
+/// 
+struct Corner {
+  to_left: bool,
+  to_top: bool,
+  to_pixel: bool,
+  min_len: (Option<StyleLength>, Option<StyleLength>),
+  max_len: (Option<StyleLength>, Option<StyleLength>),
+  lock_aspect: bool,
+  skip_set_style: bool,
+  lengths: Option<Signal<(StyleLength, StyleLength)>>,
+  start: Cell<Option<(f64, f64)>>,
+  offset: Cell<(f64, f64)>,
+}
+
+impl Corner {
+
+  /// Keep lengths of when the resize starts, and the pointer's offset if it's a drag
+  /// 
+  fn start(&self, element: &Element, e: Option<&PointerEvent>) {
+    self.start.set(Some((get_length(element, true), get_length(element, false))));
+
+    let offset = |is_lateral: bool, to_left: bool| {
+      e.and_then(|e| get_lengths(element, Step::Pointer(MouseEvent::from(e.clone()), 0.), is_lateral, to_left)).map(|(_, _, gap)| gap).unwrap_or(0.)
+    };
+    self.offset.set((offset(true, self.to_left), offset(false, self.to_top)));
+  }
+
+  /// Calculate new lengths, check limitations on each axis, and set style & signal
+  /// * Return if (width, height) changed.
+  /// 
+  fn step(&self, element: &Element, step: CornerStep) -> (bool, bool) {
+    let Some((w0, h0)) = self.start.get() else { return (false, false) };
+    let (offset_w, offset_h) = self.offset.get();
+
+    // (wrapper's length, length, gap) of each axis
+    let lengths = |is_lateral: bool, to_left: bool| {
+      let step = match &step {
+        CornerStep::Pointer(e) => Step::Pointer(e.clone(), if is_lateral { offset_w } else { offset_h }),
+        CornerStep::Gap(gap_w, gap_h) => Step::Gap(if is_lateral { *gap_w } else { *gap_h }),
+      };
+      get_lengths(element, step, is_lateral, to_left)
+    };
+    let (Some((par_w, w, gap_w)), Some((par_h, h, gap_h))) = (lengths(true, self.to_left), lengths(false, self.to_top)) else { return (false, false) };
+
+    let (mut to_w, mut to_h) = (w + gap_w, h + gap_h);
+
+    // lock aspect ratio: follow the axis which moved more (relatively)
+    if self.lock_aspect && w0>0. && h0>0. {
+      if (to_w/w0 - 1.).abs() >= (to_h/h0 - 1.).abs() {
+        to_h = to_w * h0/w0;
+      } else {
+        to_w = to_h * w0/h0;
+      }
+    }
+
+    // limitation check on each axis (with the element's own limitations)
+    let (min_w, max_w) = element_limits(element, self.min_len.0, self.max_len.0);
+    let (min_h, max_h) = element_limits(element, self.min_len.1, self.max_len.1);
+    let ok_w = to_w==w || length_check(w, to_w, par_w, min_w, max_w);
+    let ok_h = to_h==h || length_check(h, to_h, par_h, min_h, max_h);
+
+    if self.lock_aspect && !(ok_w && ok_h) { return (false, false); }
+    if !ok_w { to_w = w; }
+    if !ok_h { to_h = h; }
+    if to_w==w && to_h==h { return (false, false); }
+
+    // set style & signal
+    let (len_w, len_h) = (StyleLength::new(to_w, par_w, self.to_pixel), StyleLength::new(to_h, par_h, self.to_pixel));
+    if !self.skip_set_style {
+      let element = element.unchecked_ref::<HtmlElement>();
+      if to_w!=w { len_w.set_style(element, true); }
+      if to_h!=h { len_h.set_style(element, false); }
+    }
+    if let Some(signal) = self.lengths {
+      signal.set((len_w, len_h));
+    }
+    (to_w!=w, to_h!=h)
+  }
+
+  /// Restore lengths of when the drag started
+  /// 
+  fn restore(&self, element: &Element) {
+    let Some((w0, h0)) = self.start.get() else { return };
+    let Some(parent) = element.parent_element() else { return };
+
+    let (len_w, len_h) = (StyleLength::new(w0, scroll_length(&parent, true), self.to_pixel), StyleLength::new(h0, scroll_length(&parent, false), self.to_pixel));
+    if !self.skip_set_style {
+      let element = element.unchecked_ref::<HtmlElement>();
+      len_w.set_style(element, true);
+      len_h.set_style(element, false);
+    }
+    if let Some(signal) = self.lengths {
+      signal.set((len_w, len_h));
+    }
+  }
+}
+
+
+// component
+
+/// CornerResizerProps
+/// 
+#[derive(Props)]
+pub struct CornerResizerProps {
+  moving: Option<Signal<bool>>,
+  lengths: Option<Signal<(StyleLength, StyleLength)>>,
+  class: &'static str,
+  to_left: bool,
+  to_top: bool,
+  to_pixel: bool,
+  min_len: Option<(Option<StyleLength>, Option<StyleLength>)>,
+  max_len: Option<(Option<StyleLength>, Option<StyleLength>)>,
+  lock_aspect: Option<bool>,
+  change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+  skip_set_style: Option<bool>,
+  key_step: Option<(StyleLength, StyleLength)>,
+  enabled: Option<ReadSignal<bool>>,
+  drag_cursor: Option<&'static str>,
+  shield: Option<bool>,
+  on_resize_start: Option<ResizeCallback>,
+  on_resize: Option<ResizeCallback>,
+  on_resize_end: Option<ResizeCallback>,
+  history: Option<LayoutHistory>,
+}
+
+/// CornerResizerComponent
+/// * Component of CornerResizer
+/// * Look at `CornerResizer`'s `set_corner_resizer` for more information.
+/// 
+/// # Props
+/// * moving: Option<Signal<bool>>,
+/// * lengths: Option<Signal<(StyleLength, StyleLength)>>,
+/// * class: &'static str,
+/// * to_left: bool,
+/// * to_top: bool,
+/// * to_pixel: bool,
+/// * min_len: Option<(Option<StyleLength>, Option<StyleLength>)>,
+/// * max_len: Option<(Option<StyleLength>, Option<StyleLength>)>,
+/// * lock_aspect: Option<bool>,
+/// * change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+/// * skip_set_style: Option<bool>,
+/// * key_step: Option<(StyleLength, StyleLength)>,
+/// * enabled: Option<ReadSignal<bool>>,
+/// * drag_cursor: Option<&'static str>,
+/// * shield: Option<bool>,
+/// * on_resize_start: Option<ResizeCallback>,
+/// * on_resize: Option<ResizeCallback>,
+/// * on_resize_end: Option<ResizeCallback>,
+/// * history: Option<LayoutHistory>,
+/// 
+/// The resizer element is rendered focusable (tabindex).
+/// 
+#[component]
+pub fn CornerResizerComponent<G: Html>(props: CornerResizerProps) -> View<G> {
+
+  let rf = create_node_ref();
+
+  CornerResizer {
+    to_left: props.to_left,
+    to_top: props.to_top,
+    to_pixel: props.to_pixel,
+    min_len: props.min_len.unwrap_or_default(),
+    max_len: props.max_len.unwrap_or_default(),
+    lock_aspect: props.lock_aspect.unwrap_or(false),
+    change_class_on_move: props.change_class_on_move,
+    resizer_rf: rf,
+    key_step: props.key_step,
+    enabled: props.enabled,
+    drag_cursor: props.drag_cursor,
+    shield: props.shield.unwrap_or(false),
+    on_resize_start: props.on_resize_start,
+    on_resize: props.on_resize,
+    on_resize_end: props.on_resize_end,
+    history: props.history,
+  }.set_corner_resizer(props.moving, props.lengths, props.skip_set_style.unwrap_or(false));
+
+  view! {
+    div(ref=rf, class=props.class, tabindex="0")
+  }
+}
@@ -0,0 +1,185 @@
+use super::*;
+
+
+/// ResizeEvent
+/// * Passed to the resize lifecycle callbacks: on_resize_start, on_resize and on_resize_end.
+/// 
+/// # Fields
+/// * start: length of the resizer's panel/parcel when the resize started.
+/// * current: its current length.
+/// * delta: current - start (px).
+/// * is_lateral: axis of the resize.
+/// * parcels_before: (parcels only) lengths of parcels in the wrap when the resize started, keyed by parcel-element's dataset value.
+/// * parcels_after: (parcels only) their current lengths.
+///   * Both are empty for panel type, or when `parcel_name` is not given.
+/// 
+/// # Why string keys
+/// The callbacks are props of `Resizer`, which is shared by the panel and parcels types and is not generic over
+/// the parcel key `P` of `set_parcels_resizer`. So the maps keep the dataset values as they are read from the elements.
+/// Use `parcels_before_as`/`parcels_after_as` to parse them into `P`, as `parcel_lengths` does.
+/// 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResizeEvent {
+  pub start: StyleLength,
+  pub current: StyleLength,
+  pub delta: f64,
+  pub is_lateral: bool,
+  pub parcels_before: HashMap<String, StyleLength>,
+  pub parcels_after: HashMap<String, StyleLength>,
+}
+
+impl ResizeEvent {
+
+  /// `parcels_before` keyed by `P`. Names that fail to parse are skipped.
+  pub fn parcels_before_as<P>(&self) -> HashMap<P, StyleLength>
+  where P: std::cmp::Eq + std::hash::Hash + FromStr
+  {
+    parse_keys(&self.parcels_before)
+  }
+
+  /// `parcels_after` keyed by `P`. Names that fail to parse are skipped.
+  pub fn parcels_after_as<P>(&self) -> HashMap<P, StyleLength>
+  where P: std::cmp::Eq + std::hash::Hash + FromStr
+  {
+    parse_keys(&self.parcels_after)
+  }
+}
+
+fn parse_keys<P>(parcels: &HashMap<String, StyleLength>) -> HashMap<P, StyleLength>
+where P: std::cmp::Eq + std::hash::Hash + FromStr
+{
+  parcels.iter().filter_map(|(name, len)| Some((name.parse::<P>().ok()?, *len))).collect()
+}
+
+
+/// ResizeCallback
+/// * Callback of resize lifecycle. Clones share the same closure.
+/// 
+#[derive(Clone)]
+pub struct ResizeCallback(Rc<dyn Fn(&ResizeEvent)>);
+
+impl ResizeCallback {
+  pub fn new(f: impl Fn(&ResizeEvent) + 'static) -> Self {
+    Self(Rc::new(f))
+  }
+
+  pub fn call(&self, event: &ResizeEvent) {
+    (self.0)(event)
+  }
+}
+
+impl std::fmt::Debug for ResizeCallback {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str("ResizeCallback")
+  }
+}
+
+
+/// Track a resize from its start to end, and call the lifecycle callbacks
+/// 
+pub(crate) struct ResizeTracker<G: GenericNode> {
+  resizer_rf: NodeRef<G>,
+  is_lateral: bool,
+  to_pixel: bool,
+  parcel_name: Option<&'static str>,
+  on_resize_start: Option<ResizeCallback>,
+  on_resize: Option<ResizeCallback>,
+  on_resize_end: Option<ResizeCallback>,
+  // (start length(px), start length, parcels' start lengths)
+  started: RefCell<Option<(f64, StyleLength, HashMap<String, StyleLength>)>>,
+}
+
+impl<G: GenericNode> ResizeTracker<G> {
+
+  pub(crate) fn new(
+    resizer_rf: NodeRef<G>,
+    is_lateral: bool,
+    to_pixel: bool,
+    parcel_name: Option<&'static str>,
+    on_resize_start: Option<ResizeCallback>,
+    on_resize: Option<ResizeCallback>,
+    on_resize_end: Option<ResizeCallback>
+  ) -> Self {
+    Self { resizer_rf, is_lateral, to_pixel, parcel_name, on_resize_start, on_resize, on_resize_end, started: RefCell::new(None) }
+  }
+
+  fn is_listened(&self) -> bool {
+    self.on_resize_start.is_some() || self.on_resize.is_some() || self.on_resize_end.is_some()
+  }
+
+  /// Current length(px and StyleLength) of resizer's panel/parcel
+  fn length(&self) -> Option<(f64, StyleLength)> {
+    let element = resizer_parent_element(self.resizer_rf)?;
+    let par_len = scroll_length(&element.parent_element()?, self.is_lateral);
+    let len = get_length(&element, self.is_lateral);
+    Some((len, StyleLength::new(len, par_len, self.to_pixel)))
+  }
+
+  fn parcels(&self) -> HashMap<String, StyleLength> {
+    let Some(parcel_name) = self.parcel_name else { return HashMap::new() };
+    collect_parcel_lengths(self.resizer_rf, self.is_lateral, self.to_pixel, parcel_name).into_iter().collect()
+  }
+
+  fn event(&self) -> Option<ResizeEvent> {
+    let (len, current) = self.length()?;
+    let started = self.started.borrow();
+    let (start_len, start, parcels_before) = started.as_ref()?;
+
+    Some(ResizeEvent {
+      start: *start,
+      current,
+      delta: len - start_len,
+      is_lateral: self.is_lateral,
+      parcels_before: parcels_before.clone(),
+      parcels_after: self.parcels(),
+    })
+  }
+
+  pub(crate) fn start(&self) {
+    if !self.is_listened() { return; }
+    let Some((len, start)) = self.length() else { return };
+    *self.started.borrow_mut() = Some((len, start, self.parcels()));
+
+    if let (Some(cb), Some(event)) = (&self.on_resize_start, self.event()) {
+      cb.call(&event);
+    }
+  }
+
+  pub(crate) fn step(&self) {
+    if let (Some(cb), Some(event)) = (&self.on_resize, self.event()) {
+      cb.call(&event);
+    }
+  }
+
+  pub(crate) fn end(&self) {
+    let event = self.event();
+    if self.started.borrow_mut().take().is_none() { return; }
+
+    if let (Some(cb), Some(event)) = (&self.on_resize_end, event) {
+      cb.call(&event);
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parcels_parsed_into_key() {
+    let event = ResizeEvent {
+      start: StyleLength::Percent(30.),
+      current: StyleLength::Percent(40.),
+      delta: 10.,
+      is_lateral: true,
+      parcels_before: [("0", 30.), ("1", 70.), ("nav", 0.)].into_iter().map(|(k, v)| (k.to_string(), StyleLength::Percent(v))).collect(),
+      parcels_after: [("0", 40.), ("1", 60.)].into_iter().map(|(k, v)| (k.to_string(), StyleLength::Percent(v))).collect(),
+    };
+
+    let before = event.parcels_before_as::<usize>();
+    assert_eq!(before.len(), 2);
+    assert_eq!(before.get(&1), Some(&StyleLength::Percent(70.)));
+    assert_eq!(event.parcels_after_as::<usize>().get(&0), Some(&StyleLength::Percent(40.)));
+  }
+}
@@ -0,0 +1,324 @@
+use super::*;
+
+
+/// How `ParcelsGroup` redistributes lengths when parcels are added or removed
+/// * SplitNeighbor: an added parcel takes half of its neighbour's length, and a removed one's length goes to its neighbour.
+/// * Even: an added parcel takes an even share(1/n) from all the others, and a removed one's length is shared by all in proportion.
+/// * Remember: an added parcel restores the length it had when it was removed (or an even share if unknown),
+///   and a removed one's length is shared by all in proportion.
+/// 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RebalancePolicy {
+  #[default] SplitNeighbor,
+  Even,
+  Remember,
+}
+
+
+/// Parcels group: keeps parcels' lengths filling the wrap when parcels are added or removed
+/// 
+/// # Description
+/// * Parcels type resizers keep inline lengths of parcels. When a parcel is added or removed at runtime,
+///   the total would overflow the wrap or leave a gap.
+/// * The group observes children of the wrap, and on any change redistributes lengths under the `policy`, so the total is 100% of the wrap.
+/// 
+///   ```
+///     /* example structure*/
+///     // wrap (wrap_rf) { display: flex; }
+///     //   parcel[data-parcel="a"] { resizer }
+///     //   parcel[data-parcel="b"] { resizer }
+///     //   parcel[data-parcel="c"] (added at runtime)
+///   ```
+/// 
+/// # Use
+/// Build the struct in raw format or via method `new()`, and then call `set_parcels_group`.
+/// * Give it the same `parcel_lengths` signal as the parcels' resizers, and it's updated to match.
+/// 
+/// # Fields
+/// * wrap_rf: the NodeRef of the wrapping element of parcels.
+/// * is_lateral: is the wrap laid out horizontally? (Otherwise vertically)
+/// * to_pixel: set style in pixel or percent?
+/// * parcel_name: the name of parcel-element's dataset to identify each parcel. Children without it are not parcels.
+/// * policy: how to redistribute lengths. (Look at `RebalancePolicy`)
+/// 
+#[derive(Debug, Clone)]
+pub struct ParcelsGroup<G: GenericNode> {
+  pub wrap_rf: NodeRef<G>,
+  pub is_lateral: bool,
+  pub to_pixel: bool,
+  pub parcel_name: &'static str,
+  pub policy: RebalancePolicy,
+}
+
+impl<G: GenericNode> ParcelsGroup<G> {
+
+  pub fn new(wrap_rf: NodeRef<G>, is_lateral: bool, to_pixel: bool, parcel_name: &'static str, policy: RebalancePolicy) -> Self {
+    Self { wrap_rf, is_lateral, to_pixel, parcel_name, policy }
+  }
+
+  /// Observe the wrap's children and rebalance parcels on change
+  /// * The observer is disconnected on clean-up.
+  /// 
+  /// # Args
+  /// * parcel_lengths: removed parcels are taken out of it, and the rebalanced lengths are updated into it.
+  /// * skip_set_style(bool): If it's true, css property wouldn't be changed: only given signal would be updated.
+  /// 
+  pub fn set_parcels_group<P>(self, parcel_lengths: Option<Signal<HashMap<P, StyleLength>>>, skip_set_style: bool)
+  where P: std::cmp::Eq + std::hash::Hash + FromStr + 'static
+  {
+    let Self { wrap_rf, is_lateral, to_pixel, parcel_name, policy } = self;
+
+    on_mount(move || {
+
+      let Some(wrap) = dom_node(wrap_rf).map(|x| x.unchecked_into::<Element>()) else { return };
+
+      // parcels of the last change, and lengths(percent) of removed parcels
+      let known = RefCell::new(group_parcels(&wrap, parcel_name));
+      let remembered: RefCell<HashMap<String, f64>> = RefCell::new(HashMap::new());
+
+      let cb_mutation = Closure::<dyn FnMut(_)>::new({
+        let wrap = wrap.clone();
+        move |_: JsValue| {
+          let current = group_parcels(&wrap, parcel_name);
+          let prev = known.replace(current.clone());
+          let Some((removed, lengths)) = rebalance(&wrap, is_lateral, to_pixel, policy, &prev, &current, &remembered) else { return };
+
+          if !skip_set_style {
+            current.iter().zip(&lengths).for_each(|((_, element), style_len)| style_len.set_style(element.unchecked_ref(), is_lateral));
+          }
+          if let Some(signal) = parcel_lengths {
+            signal.update(|map| {
+              removed.iter().filter_map(|name| name.parse::<P>().ok()).for_each(|key| { map.remove(&key); });
+              map.extend(current.iter().zip(&lengths).filter_map(|((name, _), style_len)| Some((name.parse::<P>().ok()?, *style_len))));
+            });
+          }
+        }
+      });
+      let mutation_observer = MutationObserver::new(cb_mutation.as_ref().unchecked_ref()).unwrap_throw();
+      let options = MutationObserverInit::new();
+      options.set_child_list(true);
+      mutation_observer.observe_with_options(&wrap, &options).unwrap_throw();
+
+      on_cleanup(move || {
+        mutation_observer.disconnect();
+        drop(cb_mutation);
+      });
+    });
+  }
+}
+
+
+/// Parcels in the wrap: (dataset value, element)
+/// 
+pub(crate) fn group_parcels(wrap: &Element, parcel_name: &str) -> Vec<(String, Element)> {
+  let mut parcels = Vec::new();
+  let mut child = wrap.first_element_child();
+  while let Some(element) = child {
+    child = element.next_element_sibling();
+    if let Some(name) = element.unchecked_ref::<HtmlElement>().dataset().get(parcel_name) {
+      parcels.push((name, element));
+    }
+  }
+  parcels
+}
+
+/// Inline length(px) of element
+/// 
+fn inline_length(element: &Element, is_lateral: bool, par_len: f64) -> Option<f64> {
+  let value = element.unchecked_ref::<HtmlElement>().style().get_property_value(if is_lateral { "width" } else { "height" }).ok()?;
+  match value.parse::<StyleLength>().ok()? {
+    StyleLength::Null => None,
+    style_len => Some(style_len.to_pixel(par_len)),
+  }
+}
+
+/// Redistribute lengths of current parcels
+/// * Return (names of removed parcels, new lengths of current parcels), or None if nothing changed.
+/// 
+fn rebalance(
+  wrap: &Element,
+  is_lateral: bool,
+  to_pixel: bool,
+  policy: RebalancePolicy,
+  prev: &[(String, Element)],
+  current: &[(String, Element)],
+  remembered: &RefCell<HashMap<String, f64>>
+) -> Option<(Vec<String>, Vec<StyleLength>)> {
+
+  let par_len = scroll_length(wrap, is_lateral);
+  if par_len<=0. { return None; }
+
+  // a removed element is out of layout: only its inline length is known.
+  let prev: Vec<(String, Option<f64>)> = prev.iter().map(|(name, element)| (name.clone(), inline_length(element, is_lateral, par_len))).collect();
+  let current: Vec<(String, f64)> = current.iter()
+    .map(|(name, element)| (name.clone(), inline_length(element, is_lateral, par_len).unwrap_or_else(|| get_length(element, is_lateral))))
+    .collect();
+
+  let (removed, lens) = group_lengths(policy, par_len, &prev, &current, &mut remembered.borrow_mut())?;
+  Some((removed, lens.into_iter().map(|x| StyleLength::new(x, par_len, to_pixel)).collect()))
+}
+
+/// Redistribute lengths(px) of current parcels under the policy
+/// * prev: parcels of the last change, with their inline lengths. (Only removed ones' are used)
+/// * current: parcels now, with their lengths. (Added ones' are ignored)
+/// * remembered: lengths(percent) of removed parcels. Updated with the ones removed now.
+/// * Return (names of removed parcels, new lengths of current parcels), or None if nothing changed.
+/// 
+fn group_lengths(
+  policy: RebalancePolicy,
+  par_len: f64,
+  prev: &[(String, Option<f64>)],
+  current: &[(String, f64)],
+  remembered: &mut HashMap<String, f64>
+) -> Option<(Vec<String>, Vec<f64>)> {
+
+  let added: Vec<bool> = current.iter().map(|(name, _)| !prev.iter().any(|(x, _)| x==name)).collect();
+  let removed: Vec<usize> = (0..prev.len()).filter(|i| !current.iter().any(|(x, _)| *x==prev[*i].0)).collect();
+  if removed.is_empty() && !added.contains(&true) { return None; }
+
+  // lengths(px) of parcels kept from the last change
+  let mut lens: Vec<Option<f64>> = current.iter().zip(&added).map(|((_, len), added)| if *added { None } else { Some(*len) }).collect();
+
+  // removed: remember, and give its length to the neighbour
+  for i in removed.iter().copied() {
+    let (name, len) = &prev[i];
+    // unknown ones take an even share when added back.
+    let Some(len) = *len else {
+      remembered.remove(name);
+      continue;
+    };
+    remembered.insert(name.clone(), len/par_len*100.);
+
+    if let RebalancePolicy::SplitNeighbor = policy {
+      let neighbour = prev[..i].iter().rev().chain(prev[i+1..].iter())
+        .find_map(|(x, _)| current.iter().position(|(y, _)| x==y).filter(|j| lens[*j].is_some()));
+      if let Some(j) = neighbour {
+        lens[j] = lens[j].map(|x| x + len);
+      }
+    }
+  }
+
+  // added: take its length
+  let even = par_len/current.len() as f64;
+  for i in (0..current.len()).filter(|i| added[*i]) {
+    lens[i] = Some(match policy {
+      RebalancePolicy::SplitNeighbor => {
+        let neighbour = (0..i).rev().chain(i+1..current.len()).find(|j| lens[*j].is_some() && !added[*j]);
+        match neighbour {
+          Some(j) => {
+            let half = lens[j].unwrap_or(0.)/2.;
+            lens[j] = Some(half);
+            half
+          },
+          None => even,
+        }
+      },
+      RebalancePolicy::Even => even,
+      RebalancePolicy::Remember => remembered.get(&current[i].0).map(|x| x*par_len/100.).unwrap_or(even),
+    }.min(par_len));
+  }
+
+  // fill the wrap: added ones keep their lengths(except SplitNeighbor), and the others are scaled in proportion
+  let fixed = |i: usize| added[i] && policy!=RebalancePolicy::SplitNeighbor;
+  let lens: Vec<f64> = lens.into_iter().map(|x| x.unwrap_or(0.)).collect();
+  let fixed_sum: f64 = (0..lens.len()).filter(|i| fixed(*i)).map(|i| lens[i]).sum();
+  let rest_sum: f64 = (0..lens.len()).filter(|i| !fixed(*i)).map(|i| lens[i]).sum();
+
+  let lens: Vec<f64> = if rest_sum>0. {
+    let ratio = (par_len - fixed_sum).max(0.)/rest_sum;
+    lens.iter().enumerate().map(|(i, x)| if fixed(i) { *x } else { x*ratio }).collect()
+  } else if fixed_sum>0. {
+    lens.iter().map(|x| x*par_len/fixed_sum).collect()
+  } else {
+    vec![even; lens.len()]
+  };
+
+  Some((removed.into_iter().map(|i| prev[i].0.clone()).collect(), lens))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn approx(a: &[f64], b: &[f64]) -> bool {
+    a.len()==b.len() && a.iter().zip(b).all(|(x, y)| (x-y).abs()<1e-6)
+  }
+
+  fn prev(lens: &[(&str, f64)]) -> Vec<(String, Option<f64>)> {
+    lens.iter().map(|(name, len)| (name.to_string(), Some(*len))).collect()
+  }
+
+  fn current(lens: &[(&str, f64)]) -> Vec<(String, f64)> {
+    lens.iter().map(|(name, len)| (name.to_string(), *len)).collect()
+  }
+
+  #[test]
+  fn unchanged() {
+    let lens = [("a", 600.), ("b", 400.)];
+    assert_eq!(group_lengths(RebalancePolicy::Even, 1000., &prev(&lens), &current(&lens), &mut HashMap::new()), None);
+  }
+
+  #[test]
+  fn split_neighbor() {
+    // added: takes half of its neighbour
+    let (removed, lens) = group_lengths(RebalancePolicy::SplitNeighbor, 1000.,
+      &prev(&[("a", 600.), ("b", 400.)]), &current(&[("a", 600.), ("b", 400.), ("c", 0.)]), &mut HashMap::new()).unwrap();
+    assert!(removed.is_empty());
+    assert!(approx(&lens, &[600., 200., 200.]), "{lens:?}");
+
+    // removed: its length goes to the previous neighbour
+    let (removed, lens) = group_lengths(RebalancePolicy::SplitNeighbor, 1000.,
+      &prev(&[("a", 500.), ("b", 300.), ("c", 200.)]), &current(&[("a", 500.), ("c", 200.)]), &mut HashMap::new()).unwrap();
+    assert_eq!(removed, ["b"]);
+    assert!(approx(&lens, &[800., 200.]), "{lens:?}");
+
+    // the first one removed: the next one takes it
+    let (_, lens) = group_lengths(RebalancePolicy::SplitNeighbor, 1000.,
+      &prev(&[("a", 500.), ("b", 300.), ("c", 200.)]), &current(&[("b", 300.), ("c", 200.)]), &mut HashMap::new()).unwrap();
+    assert!(approx(&lens, &[800., 200.]), "{lens:?}");
+  }
+
+  #[test]
+  fn even() {
+    // added: takes 1/n, and the others are scaled in proportion
+    let (_, lens) = group_lengths(RebalancePolicy::Even, 900.,
+      &prev(&[("a", 600.), ("b", 300.)]), &current(&[("a", 600.), ("b", 300.), ("c", 0.)]), &mut HashMap::new()).unwrap();
+    assert!(approx(&lens, &[400., 200., 300.]), "{lens:?}");
+
+    // removed: shared by all in proportion
+    let (_, lens) = group_lengths(RebalancePolicy::Even, 1000.,
+      &prev(&[("a", 400.), ("b", 500.), ("c", 100.)]), &current(&[("a", 400.), ("c", 100.)]), &mut HashMap::new()).unwrap();
+    assert!(approx(&lens, &[800., 200.]), "{lens:?}");
+  }
+
+  #[test]
+  fn remember() {
+    let mut remembered = HashMap::new();
+    let (removed, lens) = group_lengths(RebalancePolicy::Remember, 1000.,
+      &prev(&[("a", 400.), ("b", 500.), ("c", 100.)]), &current(&[("a", 400.), ("c", 100.)]), &mut remembered).unwrap();
+    assert_eq!(removed, ["b"]);
+    assert!(approx(&lens, &[800., 200.]), "{lens:?}");
+    assert_eq!(remembered.get("b"), Some(&50.));
+
+    // added back: restores its length
+    let (_, lens) = group_lengths(RebalancePolicy::Remember, 1000.,
+      &prev(&[("a", 800.), ("c", 200.)]), &current(&[("a", 800.), ("b", 0.), ("c", 200.)]), &mut remembered).unwrap();
+    assert!(approx(&lens, &[400., 500., 100.]), "{lens:?}");
+
+    // unknown one: an even share
+    let (_, lens) = group_lengths(RebalancePolicy::Remember, 1000.,
+      &prev(&[("a", 500.), ("b", 500.)]), &current(&[("a", 500.), ("b", 500.), ("d", 0.), ("e", 0.)]), &mut remembered).unwrap();
+    assert!(approx(&lens, &[250., 250., 250., 250.]), "{lens:?}");
+  }
+
+  #[test]
+  fn removed_without_inline_length_is_forgotten() {
+    let mut remembered = HashMap::from_iter([("b".to_string(), 30.)]);
+    let prev = vec![("a".to_string(), Some(500.)), ("b".to_string(), None)];
+    let (removed, lens) = group_lengths(RebalancePolicy::Remember, 1000., &prev, &current(&[("a", 500.)]), &mut remembered).unwrap();
+    assert_eq!(removed, ["b"]);
+    assert!(approx(&lens, &[1000.]), "{lens:?}");
+    assert!(remembered.is_empty());
+  }
+}
@@ -1,6 +1,44 @@
 use super::*;
 
 
+/// Collapse state of panel
+/// * threshold: dragging the panel under it collapses the panel.
+/// * rail: length of collapsed panel. (zero if not given)
+/// * expanded: the last expanded length, to which the panel is restored.
+/// 
+pub struct Collapse {
+  threshold: StyleLength,
+  rail: Option<StyleLength>,
+  pub collapsed: Cell<bool>,
+  expanded: Cell<StyleLength>,
+}
+
+impl Collapse {
+
+  pub fn new(threshold: StyleLength, rail: Option<StyleLength>) -> Self {
+    Self { threshold, rail, collapsed: Cell::new(false), expanded: Cell::new(StyleLength::Null) }
+  }
+
+  /// (collapsed, the last expanded length)
+  pub fn state(&self) -> (bool, StyleLength) {
+    (self.collapsed.get(), self.expanded.get())
+  }
+
+  pub fn set_state(&self, (collapsed, expanded): (bool, StyleLength)) {
+    self.collapsed.set(collapsed);
+    self.expanded.set(expanded);
+  }
+
+  /// Collapse, remembering the current length
+  /// 
+  fn collapse(&self, len: f64, par_len: f64, to_pixel: bool) -> StyleLength {
+    self.expanded.set(StyleLength::new(len, par_len, to_pixel));
+    self.collapsed.set(true);
+    self.rail.unwrap_or(StyleLength::new(0., par_len, to_pixel))
+  }
+}
+
+
 pub fn handle_panel_mousemove<G: GenericNode>(
   is_lateral: bool, 
   to_left: bool,
@@ -8,36 +46,150 @@ pub fn handle_panel_mousemove<G: GenericNode>(
   min_len: Option<StyleLength>, 
   max_len: Option<StyleLength>, 
   resizer_rf: NodeRef<G>, 
-  e: MouseEvent, 
-  skip_set_style: bool
+  step: Step, 
+  skip_set_style: bool,
+  collapse: Option<&Collapse>,
+  clamp: Option<&Cell<bool>>
 )
 -> Option<StyleLength>
 {
   // resizer's parent element
   let Some(element) = resizer_parent_element(resizer_rf) else { return None };
+  let (min_len, max_len) = element_limits(&element, min_len, max_len);
+
+  // exact length(Home/End) skips the gap arithmetic and the limitation check
+  let exact = if let Step::To(to_len) = step { Some(to_len) } else { None };
 
   // calculate new length (px)
-  let Some((par_len, len, gap)) = get_lengths(&element, e, is_lateral, to_left) else { return None };
+  let Some((par_len, len, gap)) = get_lengths(&element, step, is_lateral, to_left) else { return None };
   if gap==0. { return None; }
-  let (to_len, is_expanding) = (len + gap, gap>0.);
+  let (mut to_len, is_expanding) = (exact.unwrap_or(len + gap), gap>0.);
+
+  // collapse check
+  if let Some(collapse) = collapse {
+    let under_threshold = !collapse.threshold.min_check(to_len.max(0.), par_len);
+
+    if collapse.collapsed.get() {
+      // stay collapsed until dragged over the threshold. Then expand at least to min limitation.
+      if !is_expanding || under_threshold { return None; }
+      to_len = to_len.max(min_bound(min_len, par_len));
+
+    } else if !is_expanding && under_threshold {
+      let style_len = collapse.collapse(len, par_len, to_pixel);
+      if !skip_set_style {
+        style_len.set_style(&element.unchecked_into(), is_lateral);
+      }
+      return Some(style_len);
+    }
+  }
 
   // limitation check
-  // (1)
-  if to_len<0. || par_len<to_len { return None; }
+  if let Some(clamped) = clamp {
+    // clamp mode: pin at the boundary
+    let pinned = if is_expanding { to_len.min(max_bound(max_len, par_len)) } else { to_len.max(min_bound(min_len, par_len)) };
+    clamped.set(pinned!=to_len);
+    if (pinned-len)*gap<=0. { return None; }
+    to_len = pinned;
 
-  // (2)
-  if !(if is_expanding {
-    max_len.map(|x| x.max_check(to_len, par_len)).unwrap_or(true)
-  } else {
-    min_len.map(|x| x.min_check(to_len, par_len)).unwrap_or(true) 
-  }) {
+  } else if exact.is_none() && !length_check(len, to_len, par_len, min_len, max_len) {
     return None;
   }
 
   // set style
   let style_len = StyleLength::new(to_len, par_len, to_pixel);
+  if !skip_set_style {
+    style_len.set_style(&element.unchecked_into(), is_lateral);
+  }
+  if let Some(collapse) = collapse {
+    collapse.collapsed.set(false);
+  }
+  Some(style_len)
+}
+
+
+/// Toggle panel between collapsed and the last expanded length
+/// 
+pub fn toggle_panel_collapse<G: GenericNode>(
+  is_lateral: bool,
+  to_pixel: bool,
+  min_len: Option<StyleLength>,
+  resizer_rf: NodeRef<G>,
+  skip_set_style: bool,
+  collapse: &Collapse
+) -> Option<StyleLength>
+{
+  let Some(element) = resizer_parent_element(resizer_rf) else { return None };
+  let Some(parent) = element.parent_element() else { return None };
+  let par_len = scroll_length(&parent, is_lateral);
+  let (min_len, _) = element_limits(&element, min_len, None);
+
+  let style_len = if collapse.collapsed.get() {
+    collapse.collapsed.set(false);
+    match collapse.expanded.get() {
+      StyleLength::Null => StyleLength::new(min_bound(min_len, par_len), par_len, to_pixel),
+      expanded => expanded,
+    }
+  } else {
+    collapse.collapse(get_length(&element, is_lateral), par_len, to_pixel)
+  };
+
+  if !skip_set_style {
+    style_len.set_style(&element.unchecked_into(), is_lateral);
+  }
+  Some(style_len)
+}
+
+
+/// Clamp the panel's length between its limitations, if it's no longer valid (e.g. after the wrap is resized)
+/// * Return the clamped length, or None if nothing changed.
+/// 
+pub fn clamp_panel_length<G: GenericNode>(
+  is_lateral: bool,
+  to_pixel: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  resizer_rf: NodeRef<G>,
+  skip_set_style: bool
+) -> Option<StyleLength>
+{
+  let element = resizer_parent_element(resizer_rf)?;
+  let par_len = scroll_length(&element.parent_element()?, is_lateral);
+  if par_len<=0. { return None; }
+  let (min_len, max_len) = element_limits(&element, min_len, max_len);
+
+  let to_len = clamp_length(get_length(&element, is_lateral), par_len, min_len, max_len)?;
+  let style_len = StyleLength::new(to_len, par_len, to_pixel);
+  if !skip_set_style {
+    style_len.set_style(&element.unchecked_into(), is_lateral);
+  }
+  Some(style_len)
+}
+
+/// Apply given length to the panel, if it passes the limitation check
+/// * (1) between [0 ~ wrapping element's length], (2) between min_len and max_len (or the element's own ones).
+/// 
+pub fn apply_panel_length<G: GenericNode>(
+  is_lateral: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  resizer_rf: NodeRef<G>,
+  style_len: StyleLength,
+  skip_set_style: bool
+) -> Option<StyleLength>
+{
+  if let StyleLength::Null = style_len { return None; }
+
+  let Some(element) = resizer_parent_element(resizer_rf) else { return None };
+  let Some(parent) = element.parent_element() else { return None };
+  let par_len = scroll_length(&parent, is_lateral);
+  let (min_len, max_len) = element_limits(&element, min_len, max_len);
+
+  let len = style_len.to_pixel(par_len);
+  if len<0. || par_len<len { return None; }
+  if len<min_bound(min_len, par_len) || max_bound(max_len, par_len)<len { return None; }
+
   if !skip_set_style {
     style_len.set_style(&element.unchecked_into(), is_lateral);
   }
   Some(style_len)
-}
\ No newline at end of file
+}
@@ -7,23 +7,29 @@ pub fn handle_parcels_mousemove<G: GenericNode, P>(
   min_len: Option<StyleLength>, 
   max_len: Option<StyleLength>, 
   resizer_rf: NodeRef<G>,
-  e: MouseEvent,
+  step: Step,
   parcel_name: Option<&str>,
-  skip_set_style: bool
+  skip_set_style: bool,
+  shrink_strategy: ShrinkStrategy,
+  clamp: Option<&Cell<bool>>
 ) -> Option<HashMap<P, StyleLength>>
 where P: std::cmp::Eq + std::hash::Hash + FromStr
 {
   // resizer's parent element
   let Some(mut element) = resizer_parent_element(resizer_rf) else { return None };
 
+  // exact length(Home/End) of the parcel skips the gap arithmetic and its limitation check
+  let mut exact = if let Step::To(to_len) = step { Some(to_len) } else { None };
+
   // get current length state and mouse movement's gap
-  let Some((par_len, mut len, mut gap)) = get_lengths(&element, e, is_lateral, to_left) else { return None };
+  let Some((par_len, mut len, mut gap)) = get_lengths(&element, step, is_lateral, to_left) else { return None };
   if gap==0. { return None; }
 
   // get expanding element
   // If given element is shrinking, replace it with expanding side's element
   // and flip direction of gap & to_left.
-  if gap<0. {
+  let flipped = gap<0.;
+  if flipped {
     element = {
       let Some(element_) = (if to_left { element.previous_element_sibling() } else { element.next_element_sibling() }) else { return None };
       element_
@@ -34,48 +40,56 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr
     to_left = !to_left;
   }
 
-  // calculate new length (expanding)
-  let to_len = len + gap;
+  // siblings: shrinking side (facing) and static side (backwards)
+  let siblings = get_siblings(to_left, &element);
+  let siblings_len: Vec<f64> = siblings.iter().map(|x| get_length(x, is_lateral)).collect();
+  let floors: Vec<f64> = siblings.iter().map(|x| min_bound(element_limits(x, min_len, None).0, par_len)).collect();
 
-  // limitation check
-  // (1)
-  if par_len<to_len { return None; }
-  // (2)
-  if !max_len.map(|x| x.max_check(to_len, par_len)).unwrap_or(true) {
-    return None;
+  let (_, max_len) = element_limits(&element, None, max_len);
+
+  // clamp mode: cut the gap at the boundary,
+  // which is the expanding element's max limitation or what facing siblings can absorb.
+  if let Some(clamped) = clamp {
+    let room = (max_bound(max_len, par_len) - len).min(shrink_capacity(shrink_strategy, &siblings_len, &floors));
+    if room<gap {
+      exact = None;
+    }
+    clamped.set(room<gap);
+    gap = gap.min(room);
+    if gap<=0. { return None; }
   }
 
-  // siblings: shrinking side (facing) and static side (backwards)
+  // exact length goes to the expanding element, or to the nearest shrinking one if flipped
+  let (exact, exact_sibling) = if flipped { (None, exact) } else { (exact, None) };
 
-  // (1) shrinking
-  // calculate new length of first element which can be shrinked.
-  // others' lengths will be just recalucated from their current length.
-  // If nothing can be shrinked, return nothing.
-  let siblings = get_siblings(to_left, &element);
+  // calculate new length (expanding)
+  let to_len = exact.unwrap_or(len + gap);
 
-  let mut any_chg = false;
-
-  let siblings_to_len: Vec<f64> = siblings.iter().map(|sibling| {
-    let len = get_length(sibling, is_lateral);
-    if !any_chg {
-      // calculate shirinked new length
-      let to_len = len - gap;
-      // limitation check
-      if to_len>0. {
-        if min_len.map(|x| x.min_check(to_len, par_len)).unwrap_or(true) {
-          any_chg = true; // change any_chg
-          return to_len;
-        }
-      }
+  // limitation check (already pinned in clamp mode)
+  if clamp.is_none() && exact.is_none() {
+    // (1)
+    if par_len<to_len { return None; }
+    // (2)
+    if !max_len.map(|x| x.max_check(to_len, par_len)).unwrap_or(true) {
+      return None;
     }
-    len
-  }).collect::<Vec<_>>();
-
-  // If nothing can be shrinked, nothing can be changed.
-  if !any_chg {
-    return None;
   }
 
+  // (1) shrinking
+  // calculate new lengths of facing siblings under the shrink strategy.
+  // If they can't absorb the gap, return nothing.
+  let siblings_to_len = match exact_sibling {
+    Some(exact) => {
+      let mut siblings_to_len = siblings_len.clone();
+      siblings_to_len[0] = exact;
+      siblings_to_len
+    },
+    _ => {
+      let Some(siblings_to_len) = shrink_siblings(shrink_strategy, &siblings_len, &floors, gap) else { return None };
+      siblings_to_len
+    },
+  };
+
   // (2) static siblings
   let siblings2 = get_siblings(!to_left, &element);
   let siblings2_to_len: Vec<f64> = siblings2.iter().map(|x| get_length(x, is_lateral)).collect();
@@ -88,8 +102,8 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr
   set_parcel_style_and_update_map(&element.unchecked_into(), to_len, par_len, map.as_mut(), is_lateral, to_pixel, parcel_name, skip_set_style);
 
   // (2) siblings
-  siblings.into_iter().zip(siblings_to_len.into_iter())
-    .chain(siblings2.into_iter().zip(siblings2_to_len.into_iter()))
+  siblings.into_iter().zip(siblings_to_len)
+    .chain(siblings2.into_iter().zip(siblings2_to_len))
     .for_each(|(element, to_len)| {
 
       set_parcel_style_and_update_map(&element.unchecked_into(), to_len, par_len, map.as_mut(), is_lateral, to_pixel, parcel_name, skip_set_style);
@@ -153,4 +167,134 @@ where P: std::cmp::Eq + std::hash::Hash + FromStr
       map.insert(p, style_len);
     }
   }
-}
\ No newline at end of file
+}
+
+
+/// Clamp lengths of parcels in the wrap between their limitations, if they're no longer valid (e.g. after the wrap is resized)
+/// * Only parcels with inline length are checked: the others are left to flex layout.
+/// * The other parcels with inline length are rebalanced(scaled, within their own limitations) so that the wrap stays filled.
+/// * Return the new lengths(keyed by parsed dataset value, if parcel_name is given), or None if nothing changed.
+/// 
+pub fn clamp_parcel_lengths<G: GenericNode, P>(
+  is_lateral: bool,
+  to_pixel: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  resizer_rf: NodeRef<G>,
+  parcel_name: Option<&str>,
+  skip_set_style: bool
+) -> Option<HashMap<P, StyleLength>>
+where P: std::cmp::Eq + std::hash::Hash + FromStr
+{
+  let parcels = wrap_parcels(resizer_rf);
+  let par_len = scroll_length(&parcels.first()?.parent_element()?, is_lateral);
+  if par_len<=0. { return None; }
+
+  // parcels with inline length, and the length taken by the others
+  let mut elements: Vec<HtmlElement> = Vec::new();
+  let mut taken = 0.;
+  for element in parcels {
+    let element = element.unchecked_into::<HtmlElement>();
+    let inline = element.style().get_property_value(if is_lateral { "width" } else { "height" }).unwrap_or_default();
+    if inline.is_empty() {
+      taken += get_length(&element, is_lateral);
+    } else {
+      elements.push(element);
+    }
+  }
+  if elements.is_empty() { return None; }
+
+  let lens: Vec<f64> = elements.iter().map(|x| get_length(x, is_lateral)).collect();
+  let (floors, ceils): (Vec<f64>, Vec<f64>) = elements.iter().map(|element| {
+    let (min_len, max_len) = element_limits(element, min_len, max_len);
+    (min_bound(min_len, par_len), max_bound(max_len, par_len))
+  }).unzip();
+
+  // clamp and rebalance
+  let total = lens.iter().sum::<f64>().min((par_len - taken).max(0.));
+  let to_lens = rebalance_lengths(&lens, &floors, &ceils, total);
+  if lens.iter().zip(&to_lens).all(|(len, to_len)| (len - to_len).abs()<=CLAMP_EPSILON) { return None; }
+
+  let mut map: HashMap<P, StyleLength> = HashMap::new();
+  for (element, to_len) in elements.iter().zip(to_lens) {
+    set_parcel_style_and_update_map(element, to_len, par_len, Some(&mut map), is_lateral, to_pixel, parcel_name, skip_set_style);
+  }
+  Some(map)
+}
+
+
+/// Parcels in the wrap of resizer's parcel (or panel and its siblings, for panel type)
+/// 
+pub fn wrap_parcels<G: GenericNode>(resizer_rf: NodeRef<G>) -> Vec<Element> {
+  let Some(wrap) = resizer_parent_element(resizer_rf).and_then(|x| x.parent_element()) else { return Vec::new() };
+
+  let mut parcels: Vec<Element> = Vec::new();
+  let mut child = wrap.first_element_child();
+  while let Some(element) = child {
+    child = element.next_element_sibling();
+    parcels.push(element);
+  }
+  parcels
+}
+
+/// Collect current lengths of parcels in the wrap: <parcel-element's dataset value: length>
+/// 
+pub fn collect_parcel_lengths<G: GenericNode>(
+  resizer_rf: NodeRef<G>,
+  is_lateral: bool,
+  to_pixel: bool,
+  parcel_name: &str
+) -> Vec<(String, StyleLength)>
+{
+  wrap_parcels(resizer_rf).into_iter().filter_map(|element| {
+    let name = element.unchecked_ref::<HtmlElement>().dataset().get(parcel_name)?;
+    Some((name, StyleLength::from_rect(&element, is_lateral, to_pixel)?))
+  }).collect()
+}
+
+/// Apply given lengths to parcels in the wrap
+/// * lengths: look up the length of parcel by its dataset value.
+/// * Each length goes through the limitation check, and nothing is applied if any fails.
+/// * Return the applied lengths keyed by parsed dataset value.
+/// 
+pub fn apply_parcel_lengths<G: GenericNode, P>(
+  resizer_rf: NodeRef<G>,
+  is_lateral: bool,
+  min_len: Option<StyleLength>,
+  max_len: Option<StyleLength>,
+  parcel_name: &str,
+  lengths: impl Fn(&str) -> Option<StyleLength>,
+  skip_set_style: bool
+) -> Option<HashMap<P, StyleLength>>
+where P: std::cmp::Eq + std::hash::Hash + FromStr
+{
+  let mut applied = Vec::new();
+
+  // limitation check
+  for element in wrap_parcels(resizer_rf) {
+    let element = element.unchecked_into::<HtmlElement>();
+    let Some(name) = element.dataset().get(parcel_name) else { continue };
+    let Some(style_len) = lengths(&name) else { continue };
+    if let StyleLength::Null = style_len { continue; }
+
+    let par_len = scroll_length(&element.parent_element()?, is_lateral);
+    let (min_len, max_len) = element_limits(&element, min_len, max_len);
+    let len = style_len.to_pixel(par_len);
+    if len<0. || par_len<len { return None; }
+    if len<min_bound(min_len, par_len) || max_bound(max_len, par_len)<len { return None; }
+
+    applied.push((element, name, style_len));
+  }
+
+  // set styles & make map
+  let mut map = HashMap::new();
+  for (element, name, style_len) in applied {
+    if !skip_set_style {
+      style_len.set_style(&element, is_lateral);
+    }
+    if let Ok(p) = name.parse() {
+      map.insert(p, style_len);
+    }
+  }
+  Some(map)
+}
@@ -0,0 +1,520 @@
+use super::*;
+
+
+/// Parcels reorder's event handler setter
+/// 
+/// # Description
+/// * Dragging the handle moves its parcel to another position in the same wrap. (e.g. swapping split panes by their headers)
+/// * While dragging, a drop indicator is shown at the edge where the parcel would be dropped.
+/// * Parcels are identified by `parcel_name` dataset, the same as `Resizer`'s `set_parcels_resizer`.
+/// * The reorder doesn't move elements itself: it moves the parcel's key in the `order` signal.
+///   Render the parcels keyed from it (`Keyed`), so Sycamore moves the elements it owns.
+/// 
+///   ```
+///     /* example structure*/
+///     // wrap
+///     //   Keyed(iterable=order) {
+///     //     parcel[data-parcel="a"]
+///     //       header { reorder-handle }
+///     //       resizer
+///     //     parcel[data-parcel="b"]
+///     //       header { reorder-handle }
+///     //   }
+///   ```
+/// 
+///   * The parcel carrying the resizer changes by the order (e.g. all but the last one). Derive it from the `order` signal, too.
+/// 
+/// # Use
+/// Build the struct in raw format or via method `new()`, and then call `set_parcels_reorder`.
+/// * It returns `ResizerHandle`, the same as `Resizer`. (Look at `ResizerHandle`)
+/// * `ParcelsReorderComponent` is a Sycamore native component wrapping all the logics.
+/// 
+/// # Fields
+/// * is_lateral: is the wrap laid out horizontally? (Otherwise vertically)
+/// * parcel_name: the name of parcel-element's dataset to identify each parcel.
+/// * indicator_class: class of the drop indicator element. Without it, the indicator is painted in default color.
+/// * change_class_on_move: on moving, convert class from old to new, and vice versa on stop. (Look at `ChangeClass`)
+/// * handle_rf: the NodeRef of the drag handle element, inside its parcel.
+/// * enabled: switch the handle's event listeners on and off reactively.
+/// * drag_cursor: cursor of the whole document during a drag. Default is `grabbing`.
+/// 
+/// # How it works
+/// * Pointer drag on the handle, and Alt + arrow keys on the focused handle move the parcel.
+/// * Escape during a drag cancels it.
+/// * Keyed rendering moves the parcel element, so it carries its inline length (`StyleLength`) with it.
+/// 
+#[derive(Debug, Clone)]
+pub struct ParcelsReorder<G: GenericNode> {
+  pub is_lateral: bool,
+  pub parcel_name: &'static str,
+  pub indicator_class: Option<&'static str>,
+  pub change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+  pub handle_rf: NodeRef<G>,
+  pub enabled: Option<ReadSignal<bool>>,
+  pub drag_cursor: Option<&'static str>,
+}
+
+impl<G: GenericNode> ParcelsReorder<G> {
+
+  /// Build new struct
+  /// * Other fields are set in default. Change them directly if needed.
+  /// 
+  pub fn new(is_lateral: bool, parcel_name: &'static str, handle_rf: NodeRef<G>) -> Self {
+    Self { is_lateral, parcel_name, indicator_class: None, change_class_on_move: None, handle_rf, enabled: None, drag_cursor: None }
+  }
+
+  /// Set parcels reorder's event handlers
+  /// 
+  /// # Args (signals)
+  /// * moving: update if is it moving(dragging) or not
+  /// * order: the parcels' keys(parsed dataset values) in order. A reorder moves the parcel's key in it.
+  ///   Keys not in it are not moved.
+  /// 
+  /// # Return
+  /// * ResizerHandle: detaches and reattaches the event listeners.
+  /// 
+  pub fn set_parcels_reorder<P>(self,
+    moving: Option<Signal<bool>>,
+    order: Signal<Vec<P>>
+  ) -> ResizerHandle
+  where P: FromStr + PartialEq + 'static
+  {
+    // expand self
+    let Self { is_lateral, parcel_name, indicator_class, change_class_on_move, handle_rf, enabled, drag_cursor } = self;
+
+    // event listeners, kept until clean-up and controlled by the handle
+    let listeners = Listeners::new(enabled);
+    Listeners::keep_until_cleanup(&listeners);
+
+    // move parcel to the index among parcels (before the parcel at the index, or at the end)
+    let move_parcel = move |parcel: &Element, index: usize| {
+      let Some(wrap) = parcel.parent_element() else { return };
+      let parcels = group_parcels(&wrap, parcel_name);
+      let Some(from) = parcels.iter().position(|(_, x)| x==parcel) else { return };
+      if drop_target(from, index).is_none() { return; }
+
+      let Ok(key) = parcels[from].0.parse::<P>() else { return };
+      let before = match parcels.get(index) {
+        Some((name, _)) => match name.parse::<P>() { Ok(x) => Some(x), Err(_) => return },
+        None => None,
+      };
+      order.update(|keys| { move_key(keys, &key, before.as_ref()); });
+    };
+
+    let weak = Rc::downgrade(&listeners);
+    on_mount(move || {
+
+      let Some(listeners) = weak.upgrade() else { return };
+      let Some(target) = dom_node(handle_rf).map(|x| x.unchecked_into::<HtmlElement>()) else { return };
+
+      // keep touch dragging from being taken over by browser's panning
+      target.style().set_property("touch-action", "none").unwrap_throw();
+
+      // (pointer id, dragged parcel, drop index) of the ongoing drag
+      let active: Rc<RefCell<Option<(i32, Element, Option<usize>)>>> = Rc::new(RefCell::new(None));
+      let indicator = Rc::new(DropIndicator::new(is_lateral, indicator_class));
+
+      let lock = Rc::new(DragLock::new(drag_cursor.unwrap_or("grabbing"), false));
+
+      // pointerdown
+      let cb_pointerdown = Closure::<dyn FnMut(_)>::new({
+        let (active, target, lock) = (active.clone(), target.clone(), lock.clone());
+        move |e: PointerEvent| {
+          if active.borrow().is_some() || e.button()!=0 { return; }
+          let Some(parcel) = handle_parcel(&target, parcel_name) else { return };
+
+          e.prevent_default();
+          let _ = target.set_pointer_capture(e.pointer_id());
+          *active.borrow_mut() = Some((e.pointer_id(), parcel, None));
+
+          // convert class & set moving true
+          if let Some((old, new)) = change_class_on_move {
+            ChangeClass::replace(handle_rf, old, new, true);
+          }
+          moving.map(|x| x.set(true));
+          lock.lock();
+        }
+      });
+
+      // pointermove: find the drop index and show the indicator
+      let cb_move = Closure::<dyn FnMut(_)>::new({
+        let (active, indicator) = (active.clone(), indicator.clone());
+        move |e: PointerEvent| {
+          let mut active = active.borrow_mut();
+          let Some((pointer_id, parcel, index)) = active.as_mut() else { return };
+          if *pointer_id != e.pointer_id() { return; }
+          let Some(wrap) = parcel.parent_element() else { return };
+
+          let parcels = group_parcels(&wrap, parcel_name);
+          let position = if is_lateral { e.client_x() } else { e.client_y() } as f64;
+          let to = drop_index(&parcel_centers(&parcels, is_lateral), position);
+          let from = parcels.iter().position(|(_, x)| x==&*parcel);
+
+          match from.and_then(|from| drop_target(from, to)) {
+            Some(_) => {
+              *index = Some(to);
+              indicator.show(&parcels, to);
+            },
+            None => {
+              *index = None;
+              indicator.hide();
+            },
+          }
+        }
+      });
+
+      // end of drag: drop the parcel if dropping
+      let end_drag = Rc::new({
+        let (active, target, indicator) = (active.clone(), target.clone(), indicator.clone());
+        move |dropping: bool| {
+          let Some((pointer_id, parcel, index)) = active.take() else { return };
+          if target.has_pointer_capture(pointer_id) {
+            let _ = target.release_pointer_capture(pointer_id);
+          }
+          indicator.hide();
+
+          if let (true, Some(index)) = (dropping, index) {
+            move_parcel(&parcel, index);
+          }
+
+          // convert class & set moving false
+          if let Some((old, new)) = change_class_on_move {
+            ChangeClass::replace(handle_rf, old, new, false);
+          }
+          moving.map(|x| x.set(false));
+          lock.unlock();
+        }
+      });
+
+      // pointerup drops, and pointercancel and lostpointercapture cancel
+      let cb_pointerup = Rc::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: PointerEvent| {
+          if active.borrow().as_ref().map(|x| x.0) != Some(e.pointer_id()) { return; }
+          end_drag(e.type_()=="pointerup");
+        }
+      });
+
+      // Escape cancels the ongoing drag
+      let cb_escape = Closure::<dyn FnMut(_)>::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: KeyboardEvent| {
+          if e.key()!="Escape" || active.borrow().is_none() { return; }
+          e.prevent_default();
+          end_drag(false);
+        }
+      });
+
+      // detaching cancels the ongoing drag
+      listeners.on_detach(move || end_drag(false));
+
+      // keydown: Alt + arrows move the parcel by one
+      let cb_keydown = Closure::<dyn FnMut(_)>::new({
+        let target = target.clone();
+        move |e: KeyboardEvent| {
+          if !e.alt_key() { return; }
+          let forward = match (is_lateral, e.key().as_str()) {
+            (true, "ArrowLeft") | (false, "ArrowUp") => false,
+            (true, "ArrowRight") | (false, "ArrowDown") => true,
+            _ => return,
+          };
+          let Some(parcel) = handle_parcel(&target, parcel_name) else { return };
+          let Some(wrap) = parcel.parent_element() else { return };
+          let parcels = group_parcels(&wrap, parcel_name);
+          let Some(from) = parcels.iter().position(|(_, x)| x==&parcel) else { return };
+
+          e.prevent_default();
+          if let Some(index) = step_index(from, parcels.len(), forward) {
+            move_parcel(&parcel, index);
+          }
+          let _ = target.focus();
+        }
+      });
+
+      // set handlers (removed on clean-up, or by the handle)
+      let target: &EventTarget = &target;
+      listeners.listen(target, "keydown", cb_keydown, false);
+      listeners.listen(target, "pointerdown", cb_pointerdown, false);
+      listeners.listen(target, "pointermove", cb_move, false);
+      listeners.listen(&gloo_utils::document(), "keydown", cb_escape, false);
+      for event in POINTER_END_EVENTS {
+        listeners.listen(target, event, Closure::<dyn FnMut(_)>::new({
+          let cb_pointerup = cb_pointerup.clone();
+          move |e: PointerEvent| cb_pointerup(e)
+        }), false);
+      }
+    });
+
+    ResizerHandle(listeners)
+  }
+}
+
+
+/// Parcel element of the handle: the nearest ancestor with `parcel_name` dataset
+/// 
+fn handle_parcel(handle: &Element, parcel_name: &str) -> Option<Element> {
+  let mut element = handle.parent_element();
+  while let Some(x) = element {
+    if x.unchecked_ref::<HtmlElement>().dataset().get(parcel_name).is_some() {
+      return Some(x);
+    }
+    element = x.parent_element();
+  }
+  None
+}
+
+/// Centers of parcels on the axis
+/// 
+fn parcel_centers(parcels: &[(String, Element)], is_lateral: bool) -> Vec<f64> {
+  parcels.iter().map(|(_, x)| {
+    let rect = x.get_bounding_client_rect();
+    if is_lateral { rect.left() + rect.width()/2. } else { rect.top() + rect.height()/2. }
+  }).collect()
+}
+
+/// Index to drop at: count of parcels whose center is before the position
+/// 
+fn drop_index(centers: &[f64], position: f64) -> usize {
+  centers.iter().filter(|x| **x < position).count()
+}
+
+/// Final index of the parcel at `from` dropped at `index` (before the parcel at the index, or at the end)
+/// * None if it stays where it is.
+/// 
+fn drop_target(from: usize, index: usize) -> Option<usize> {
+  if index==from || index==from+1 { return None; }
+  Some(if index>from { index-1 } else { index })
+}
+
+/// Drop index of Alt + arrow keys: one step forward or backward among `len` parcels
+/// * None at the ends.
+/// 
+fn step_index(from: usize, len: usize, forward: bool) -> Option<usize> {
+  match forward {
+    true if from+1<len => Some(from+2),
+    false if from>0 => Some(from-1),
+    _ => None,
+  }
+}
+
+/// Move the key before the other key (or to the end if None)
+/// * Return false if the key is not in keys.
+/// 
+fn move_key<P: PartialEq>(keys: &mut Vec<P>, key: &P, before: Option<&P>) -> bool {
+  let Some(from) = keys.iter().position(|x| x==key) else { return false };
+  let item = keys.remove(from);
+  let to = before.and_then(|before| keys.iter().position(|x| x==before)).unwrap_or(keys.len());
+  keys.insert(to, item);
+  true
+}
+
+
+/// Drop indicator: a fixed positioned line at the edge of parcel, shown while dragging
+/// 
+struct DropIndicator {
+  is_lateral: bool,
+  class: Option<&'static str>,
+  element: RefCell<Option<HtmlElement>>,
+}
+
+impl DropIndicator {
+
+  fn new(is_lateral: bool, class: Option<&'static str>) -> Self {
+    Self { is_lateral, class, element: RefCell::new(None) }
+  }
+
+  /// Show at the start edge of the parcel at the index, or the end edge of the last one
+  /// 
+  fn show(&self, parcels: &[(String, Element)], index: usize) {
+    let (rect, at_end) = match parcels.get(index) {
+      Some((_, x)) => (x.get_bounding_client_rect(), false),
+      None => match parcels.last() {
+        Some((_, x)) => (x.get_bounding_client_rect(), true),
+        None => return,
+      },
+    };
+
+    let mut element = self.element.borrow_mut();
+    if element.is_none() {
+      *element = self.create();
+    }
+    let Some(element) = element.as_ref() else { return };
+
+    let thickness = INDICATOR_THICKNESS;
+    let (left, top, width, height) = if self.is_lateral {
+      let x = if at_end { rect.right() } else { rect.left() };
+      (x - thickness/2., rect.top(), thickness, rect.height())
+    } else {
+      let y = if at_end { rect.bottom() } else { rect.top() };
+      (rect.left(), y - thickness/2., rect.width(), thickness)
+    };
+
+    let style = element.style();
+    for (property, value) in [("left", left), ("top", top), ("width", width), ("height", height)] {
+      style.set_property(property, &format!("{:.2}px", value)).unwrap_throw();
+    }
+    style.remove_property("display").unwrap_throw();
+  }
+
+  fn hide(&self) {
+    if let Some(element) = self.element.borrow_mut().take() {
+      element.remove();
+    }
+  }
+
+  fn create(&self) -> Option<HtmlElement> {
+    let document = gloo_utils::document();
+    let element = document.create_element("div").ok()?.unchecked_into::<HtmlElement>();
+
+    let style = element.style();
+    style.set_property("position", "fixed").unwrap_throw();
+    style.set_property("pointer-events", "none").unwrap_throw();
+    style.set_property("z-index", "1000").unwrap_throw();
+    match self.class {
+      Some(class) => element.set_class_name(class),
+      None => style.set_property("background-color", INDICATOR_COLOR).unwrap_throw(),
+    }
+
+    document.body()?.append_child(&element).ok()?;
+    Some(element)
+  }
+}
+
+impl Drop for DropIndicator {
+  fn drop(&mut self) {
+    self.hide();
+  }
+}
+
+/// Thickness(px) of drop indicator
+const INDICATOR_THICKNESS: f64 = 4.;
+
+/// Default color of drop indicator
+const INDICATOR_COLOR: &str = "rgba(10, 120, 250, 0.5)";
+
+
+// component
+
+/// ParcelsReorderProps
+/// 
+#[derive(Props)]
+pub struct ParcelsReorderProps<P>
+where P: FromStr + PartialEq + 'static
+{
+  moving: Option<Signal<bool>>,
+  order: Signal<Vec<P>>,
+  class: &'static str,
+  is_lateral: bool,
+  parcel_name: &'static str,
+  indicator_class: Option<&'static str>,
+  change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+  enabled: Option<ReadSignal<bool>>,
+  drag_cursor: Option<&'static str>,
+}
+
+/// ParcelsReorderComponent
+/// * Drag handle of ParcelsReorder. Put it inside the parcel (e.g. in its header).
+/// * Look at `ParcelsReorder`'s `set_parcels_reorder` for more information.
+/// 
+/// # Props
+/// * moving: Option<Signal<bool>>,
+/// * order: Signal<Vec<P>>,
+/// * class: &'static str,
+/// * is_lateral: bool,
+/// * parcel_name: &'static str,
+/// * indicator_class: Option<&'static str>,
+/// * change_class_on_move: Option<(Option<&'static str>, &'static str)>,
+/// * enabled: Option<ReadSignal<bool>>,
+/// * drag_cursor: Option<&'static str>,
+/// 
+/// The handle element is rendered focusable (tabindex).
+/// 
+#[component]
+pub fn ParcelsReorderComponent<G: Html, P>(props: ParcelsReorderProps<P>) -> View<G>
+where P: FromStr + PartialEq + 'static
+{
+  let rf = create_node_ref();
+
+  ParcelsReorder {
+    is_lateral: props.is_lateral,
+    parcel_name: props.parcel_name,
+    indicator_class: props.indicator_class,
+    change_class_on_move: props.change_class_on_move,
+    handle_rf: rf,
+    enabled: props.enabled,
+    drag_cursor: props.drag_cursor,
+  }.set_parcels_reorder(props.moving, props.order);
+
+  view! {
+    div(ref=rf, class=props.class, tabindex="0")
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Reorder by index math, as the handlers do
+  fn reorder(keys: &[&str], from: usize, index: usize) -> Vec<String> {
+    let mut keys: Vec<String> = keys.iter().map(|x| x.to_string()).collect();
+    if drop_target(from, index).is_some() {
+      let (key, before) = (keys[from].clone(), keys.get(index).cloned());
+      assert!(move_key(&mut keys, &key, before.as_ref()));
+    }
+    keys
+  }
+
+  #[test]
+  fn drop_index_by_centers() {
+    let centers = [50., 150., 250.];
+    assert_eq!(drop_index(&centers, 0.), 0);
+    assert_eq!(drop_index(&centers, 100.), 1);
+    assert_eq!(drop_index(&centers, 249.), 2);
+    assert_eq!(drop_index(&centers, 300.), 3);
+  }
+
+  #[test]
+  fn drop_target_skips_own_edges() {
+    assert_eq!(drop_target(1, 1), None);
+    assert_eq!(drop_target(1, 2), None);
+    assert_eq!(drop_target(1, 0), Some(0));
+    assert_eq!(drop_target(1, 3), Some(2));
+    assert_eq!(drop_target(0, 3), Some(2));
+  }
+
+  #[test]
+  fn drop_moves() {
+    let keys = ["a", "b", "c"];
+    assert_eq!(reorder(&keys, 0, 3), ["b", "c", "a"]);
+    assert_eq!(reorder(&keys, 2, 0), ["c", "a", "b"]);
+    assert_eq!(reorder(&keys, 0, 2), ["b", "a", "c"]);
+    assert_eq!(reorder(&keys, 1, 2), ["a", "b", "c"]);
+  }
+
+  #[test]
+  fn alt_arrow_moves() {
+    let keys = ["a", "b", "c"];
+    // forward
+    assert_eq!(step_index(0, 3, true), Some(2));
+    assert_eq!(reorder(&keys, 0, 2), ["b", "a", "c"]);
+    assert_eq!(step_index(1, 3, true), Some(3));
+    assert_eq!(reorder(&keys, 1, 3), ["a", "c", "b"]);
+    assert_eq!(step_index(2, 3, true), None);
+    // backward
+    assert_eq!(step_index(2, 3, false), Some(1));
+    assert_eq!(reorder(&keys, 2, 1), ["a", "c", "b"]);
+    assert_eq!(step_index(0, 3, false), None);
+  }
+
+  #[test]
+  fn move_key_in_order() {
+    let mut keys = vec![1, 2, 3, 4];
+    assert!(move_key(&mut keys, &4, Some(&2)));
+    assert_eq!(keys, [1, 4, 2, 3]);
+    assert!(move_key(&mut keys, &1, None));
+    assert_eq!(keys, [4, 2, 3, 1]);
+    assert!(!move_key(&mut keys, &5, Some(&1)));
+    assert_eq!(keys, [4, 2, 3, 1]);
+  }
+}
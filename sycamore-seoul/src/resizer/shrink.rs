@@ -0,0 +1,186 @@
+use super::*;
+
+
+/// Shrink facing siblings by gap under the strategy.
+/// * lens: current lengths of siblings, the nearest first.
+/// * floors: lengths to which each sibling can shrink at most.
+/// * Return new lengths, or None if siblings can't absorb the gap.
+/// 
+pub(crate) fn shrink_siblings(strategy: ShrinkStrategy, lens: &[f64], floors: &[f64], gap: f64) -> Option<Vec<f64>> {
+
+  let mut to_lens = lens.to_vec();
+
+  match strategy {
+    // the first one which can absorb the whole gap
+    ShrinkStrategy::Nearest => {
+      let i = lens.iter().zip(floors).position(|(len, floor)| {
+        let to_len = len - gap;
+        to_len>0. && to_len>=*floor
+      })?;
+      to_lens[i] -= gap;
+    },
+
+    // one after another, as each reaches its floor
+    ShrinkStrategy::Cascade => {
+      let mut rest = gap;
+      for (to_len, floor) in to_lens.iter_mut().zip(floors) {
+        let take = (*to_len - floor).max(0.).min(rest);
+        *to_len -= take;
+        rest -= take;
+      }
+      if rest>SHRINK_EPSILON { return None; }
+    },
+
+    // spread by current lengths. Siblings reaching their floor pass the rest to others.
+    ShrinkStrategy::Proportional => {
+      let mut rest = gap;
+      let mut free: Vec<bool> = to_lens.iter().zip(floors).map(|(len, floor)| len>floor).collect();
+
+      while rest>SHRINK_EPSILON {
+        let total: f64 = to_lens.iter().zip(&free).filter(|(_, free)| **free).map(|(len, _)| *len).sum();
+        if total<=0. { return None; }
+
+        let shares: Vec<f64> = to_lens.iter().map(|len| rest * len / total).collect();
+        for (((to_len, floor), free), share) in to_lens.iter_mut().zip(floors).zip(free.iter_mut()).zip(shares) {
+          if !*free { continue; }
+          let take = share.min(*to_len - floor);
+          *to_len -= take;
+          rest -= take;
+          if take<share { *free = false; }
+        }
+      }
+    },
+  }
+
+  Some(to_lens)
+}
+
+/// How much facing siblings can absorb under the strategy
+/// * A bit less than exact, so that `shrink_siblings` accepts it.
+/// 
+pub(crate) fn shrink_capacity(strategy: ShrinkStrategy, lens: &[f64], floors: &[f64]) -> f64 {
+  let rooms = lens.iter().zip(floors).map(|(len, floor)| (len - floor).min(*len) - SHRINK_EPSILON);
+
+  match strategy {
+    ShrinkStrategy::Nearest => rooms.fold(0., f64::max),
+    ShrinkStrategy::Cascade | ShrinkStrategy::Proportional => rooms.map(|x| x.max(0.)).sum(),
+  }
+}
+
+/// Clamp lengths between floors and ceils, and spread the rest over the others so that they sum up to total
+/// * Lengths out of their bounds are pinned at them. The others are scaled by the same ratio,
+///   and ones going out of bounds on the way are pinned in turn.
+/// * If all of them are pinned, the sum may differ from total.
+/// 
+pub(crate) fn rebalance_lengths(lens: &[f64], floors: &[f64], ceils: &[f64], total: f64) -> Vec<f64> {
+
+  let clamp = |len: f64, i: usize| len.min(ceils[i]).max(floors[i]);
+  let mut to_lens: Vec<f64> = lens.iter().enumerate().map(|(i, len)| clamp(*len, i)).collect();
+  let mut pinned: Vec<bool> = lens.iter().zip(&to_lens).map(|(len, to_len)| (len - to_len).abs()>SHRINK_EPSILON).collect();
+
+  loop {
+    let fixed: f64 = to_lens.iter().zip(&pinned).filter(|(_, pinned)| **pinned).map(|(len, _)| *len).sum();
+    let free: f64 = to_lens.iter().zip(&pinned).filter(|(_, pinned)| !**pinned).map(|(len, _)| *len).sum();
+    let rest = (total - fixed).max(0.);
+    if free<=SHRINK_EPSILON || (rest - free).abs()<=SHRINK_EPSILON { return to_lens; }
+
+    let ratio = rest / free;
+    let mut repinned = false;
+    for (i, (to_len, pinned)) in to_lens.iter_mut().zip(pinned.iter_mut()).enumerate() {
+      if *pinned { continue; }
+      let scaled = *to_len * ratio;
+      *to_len = clamp(scaled, i);
+      if (scaled - *to_len).abs()>SHRINK_EPSILON {
+        *pinned = true;
+        repinned = true;
+      }
+    }
+    if !repinned { return to_lens; }
+  }
+}
+
+/// Tolerance of floating point error on shrinking
+const SHRINK_EPSILON: f64 = 1e-6;
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn approx(a: &[f64], b: &[f64]) -> bool {
+    a.len()==b.len() && a.iter().zip(b).all(|(x, y)| (x-y).abs()<1e-3)
+  }
+
+  #[test]
+  fn nearest_shrinks_first_one_absorbing_whole_gap() {
+    let lens = [30., 100.];
+    let floors = [20., 20.];
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Nearest, &lens, &floors, 5.).unwrap(), &[25., 100.]));
+    // the nearest can't absorb 20 (floor 20), so the next one does
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Nearest, &lens, &floors, 20.).unwrap(), &[30., 80.]));
+    assert!(shrink_siblings(ShrinkStrategy::Nearest, &lens, &floors, 90.).is_none());
+  }
+
+  #[test]
+  fn cascade_pushes_one_after_another() {
+    let lens = [30., 100., 50.];
+    let floors = [20., 20., 0.];
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Cascade, &lens, &floors, 50.).unwrap(), &[20., 60., 50.]));
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Cascade, &lens, &floors, 140.).unwrap(), &[20., 20., 0.]));
+    assert!(shrink_siblings(ShrinkStrategy::Cascade, &lens, &floors, 141.).is_none());
+  }
+
+  #[test]
+  fn proportional_spreads_by_lengths() {
+    let lens = [100., 300.];
+    let floors = [0., 0.];
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Proportional, &lens, &floors, 40.).unwrap(), &[90., 270.]));
+  }
+
+  #[test]
+  fn proportional_passes_rest_over_floors() {
+    let lens = [100., 300.];
+    let floors = [95., 0.];
+    // the first reaches its floor(5 taken), and the rest goes to the second
+    assert!(approx(&shrink_siblings(ShrinkStrategy::Proportional, &lens, &floors, 40.).unwrap(), &[95., 265.]));
+    assert!(shrink_siblings(ShrinkStrategy::Proportional, &lens, &floors, 306.).is_none());
+  }
+
+  #[test]
+  fn capacity_is_accepted_by_shrink() {
+    let lens = [30., 100., 50.];
+    let floors = [20., 20., 0.];
+    for strategy in [ShrinkStrategy::Nearest, ShrinkStrategy::Cascade, ShrinkStrategy::Proportional] {
+      let capacity = shrink_capacity(strategy, &lens, &floors);
+      assert!(shrink_siblings(strategy, &lens, &floors, capacity).is_some(), "{strategy:?}");
+    }
+    assert!((shrink_capacity(ShrinkStrategy::Nearest, &lens, &floors) - 80.).abs()<1e-3);
+    assert!((shrink_capacity(ShrinkStrategy::Cascade, &lens, &floors) - 140.).abs()<1e-3);
+  }
+
+  #[test]
+  fn rebalance_keeps_valid_lengths() {
+    let lens = [100., 300.];
+    assert!(approx(&rebalance_lengths(&lens, &[0., 0.], &[400., 400.], 400.), &lens));
+  }
+
+  #[test]
+  fn rebalance_fills_total_after_clamp() {
+    // min limitation
+    assert!(approx(&rebalance_lengths(&[100., 300.], &[150., 0.], &[400., 400.], 400.), &[150., 250.]));
+    // max limitation
+    assert!(approx(&rebalance_lengths(&[300., 100.], &[0., 0.], &[200., 400.], 400.), &[200., 200.]));
+  }
+
+  #[test]
+  fn rebalance_pins_siblings_reaching_bounds() {
+    // the second goes under its floor on the way, and the third takes the rest
+    let to_lens = rebalance_lengths(&[100., 100., 200.], &[150., 90., 0.], &[400., 400., 400.], 400.);
+    assert!(approx(&to_lens, &[150., 90., 160.]));
+  }
+
+  #[test]
+  fn rebalance_scales_to_smaller_total() {
+    assert!(approx(&rebalance_lengths(&[300., 300.], &[0., 0.], &[1000., 1000.], 400.), &[200., 200.]));
+  }
+}
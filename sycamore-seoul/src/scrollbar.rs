@@ -20,14 +20,23 @@ use crate::*;
 ///    * take orthogonal movement if configured to do so.
 ///      * If so, (b)manually scroll parent element
 /// 
-/// 5. listens to **mousedown event** at the bar.
-///    * set mousemove & mouseup event to document.
+/// 5. listens to **pointerdown event** at the bar.
+///    * capture the pointer to the bar, and follow its pointermove & pointerup events.
 ///    * while moving, (b)manually scroll parent element
+///    * Pointer Events cover mouse, pen and touch. Only the pointer which started the drag is followed.
+///    * Escape during the drag cancels it: the parent's scroll offset is restored to that of pointerdown.
+///    * With `shield`, a transparent overlay covers the viewport during the drag, so iframes under the pointer don't take its events.
 /// 
 /// - 4. and 5. does not directly listens to scroll event. Rather they triggeres scroll event, which would be captured and handled at 3.
 /// 
+/// 6. observes sizes of parent and its content (`ResizeObserver`), and changes of parent's children (`MutationObserver`).
+///    * (a)update bar's style
+///    * So the bar stays correct when content loads, or when a neighbouring resizer moves.
+/// 
 /// # Use
 /// Make a struct and then call `set_scrollbar` method.
+/// * It returns `ScrollBarHandle`, which controls the event listeners. (Look at `ScrollBarHandle`)
+///   * Listeners live until the scope is cleaned up. Keep the handle only to `detach()`/`reattach()` the scrollbar.
 /// 
 /// * Example of structure of elements:
 ///   * parent (which will be overflowed and scrolled) {position: relative}
@@ -40,8 +49,8 @@ use crate::*;
 ///   For example, if parent gets overflowed in vertical direction, a exisiting lateral scrollbar would lose its position and result in floating at the middle of parent, not marginal side.
 ///   * To prevent this, use `sync_scroll_absolute_position`. Look at demo example.
 /// 
-/// * Window's resizing would affect the scrollbar's style.
-///   * The `update_scrollbar` field can be used for this case: on any change of given signal, scrollbar's style will be udpated.
+/// * Size changes of parent and its content are observed (6.), so there's no need to wire window resizing or resizers into `update_scrollbar`.
+///   * The `update_scrollbar` field can still be used for other cases: on any change of given signal, scrollbar's style will be udpated.
 ///   * `listen_window_resize_event()` is helper function for convenient handling of window resize event and its related signal.
 /// 
 /// # Fields
@@ -52,6 +61,9 @@ use crate::*;
 /// * is_scrollable(Option<Signal<bool>>): is the parent element scrollable? (scroll-length>client-length)
 /// * is_scrolling: is it scrolling?
 /// * update_scrollbar(Option<ReadSignal<T>>): Signal to be triggered to update scrollbar's state. If it's not given, just set <T> generic as bool. 
+/// * enabled(Option<ReadSignal<bool>>): switch the event listeners on and off reactively. Set it by `with_enabled()`.
+/// * drag_cursor(Option<&'static str>): cursor of the whole document during a drag. Default is `grabbing`. Set it by `with_drag_cursor()`.
+/// * shield(bool): put up a full-viewport transparent overlay during a drag. Set it by `with_shield()`.
 /// 
 pub struct ScrollBar<G: GenericNode, T: 'static> {
   bar_rf: NodeRef<G>,
@@ -61,35 +73,63 @@ pub struct ScrollBar<G: GenericNode, T: 'static> {
   is_scrollable: Option<Signal<bool>>,
   is_scrolling: Option<Signal<bool>>,
   update_scrollbar: Option<ReadSignal<T>>,
+  enabled: Option<ReadSignal<bool>>,
+  drag_cursor: Option<&'static str>,
+  shield: bool,
 }
 
 impl<G: GenericNode, T: 'static> ScrollBar<G, T> {
 
   pub fn new(bar_rf: NodeRef<G>, is_lateral: bool, take_orthogonal: bool, min_length: Option<StyleLength>, is_scrollable: Option<Signal<bool>>, is_scrolling: Option<Signal<bool>>, update_scrollbar: Option<ReadSignal<T>>) -> Self {
-    Self { bar_rf, is_lateral, take_orthogonal, min_length, is_scrollable, is_scrolling, update_scrollbar }
+    Self { bar_rf, is_lateral, take_orthogonal, min_length, is_scrollable, is_scrolling, update_scrollbar, enabled: None, drag_cursor: None, shield: false }
+  }
+
+  pub fn with_enabled(self, enabled: ReadSignal<bool>) -> Self {
+    Self { enabled: Some(enabled), ..self }
+  }
+
+  pub fn with_drag_cursor(self, drag_cursor: &'static str) -> Self {
+    Self { drag_cursor: Some(drag_cursor), ..self }
+  }
+
+  pub fn with_shield(self, shield: bool) -> Self {
+    Self { shield, ..self }
   }
 
   /// set scrollbar logics
   /// 
-  pub fn set_scrollbar(self) {
-    let Self { bar_rf, is_lateral, take_orthogonal, min_length, is_scrollable, is_scrolling, update_scrollbar } = self;
+  /// # Return
+  /// * ScrollBarHandle: detaches and reattaches the event listeners.
+  /// 
+  pub fn set_scrollbar(self) -> ScrollBarHandle {
+    let Self { bar_rf, is_lateral, take_orthogonal, min_length, is_scrollable, is_scrolling, update_scrollbar, enabled, drag_cursor, shield } = self;
+
+    // event listeners, kept until clean-up and controlled by the handle
+    let listeners = Listeners::new(enabled);
+    Listeners::keep_until_cleanup(&listeners);
 
+    let weak = Rc::downgrade(&listeners);
     on_mount(move || {
 
+      let Some(listeners) = weak.upgrade() else { return };
+
       // init bar's style
-      if let Some(bar) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<HtmlElement>()) {
+      if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<HtmlElement>()) {
         Self::update_bar_style(is_lateral, &bar, min_length, is_scrollable);
       }
 
       // listen to update_scrollbar signal
       if let Some(signal) = update_scrollbar {
         create_effect(on(signal, move || {
-          if let Some(bar) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<HtmlElement>()) {
+          if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<HtmlElement>()) {
             Self::update_bar_style(is_lateral, &bar, min_length, is_scrollable);
           }
         }));
       }
 
+      // observe sizes of parent and its content
+      Self::observe_parent(bar_rf, is_lateral, min_length, is_scrollable, &listeners);
+
       // parent
 
       // scroll event
@@ -97,7 +137,7 @@ impl<G: GenericNode, T: 'static> ScrollBar<G, T> {
         if let Some(is_scrolling) = is_scrolling {
           is_scrolling.set(true);
         }
-        if let Some(bar) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<HtmlElement>()) {
+        if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<HtmlElement>()) {
           Self::update_bar_style(is_lateral, &bar, min_length, is_scrollable);
         }
       });
@@ -107,7 +147,7 @@ impl<G: GenericNode, T: 'static> ScrollBar<G, T> {
         
         let delta = if is_lateral { e.delta_y() } else { e.delta_x() };
         if delta != 0. {
-          if let Some(parent) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
+          if let Some(parent) = dom_node(bar_rf).map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
             Self::update_scroll(&parent, is_lateral, delta);
           }
         }
@@ -115,17 +155,31 @@ impl<G: GenericNode, T: 'static> ScrollBar<G, T> {
 
       // bar
 
-      let x = None::<f64>;
-      let x = Box::into_raw(Box::new(x));
+      // keep touch dragging from being taken over by browser's panning
+      if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<HtmlElement>()) {
+        bar.style().set_property("touch-action", "none").unwrap_throw();
+      }
+
+      // id of the pointer which is dragging the bar, and its last position
+      let active: Rc<Cell<Option<i32>>> = Rc::new(Cell::new(None));
+      let x: Rc<Cell<Option<f64>>> = Rc::new(Cell::new(None));
+
+      // parent's scroll offset at drag start, to be restored on Escape
+      let scroll0: Rc<Cell<i32>> = Rc::new(Cell::new(0));
 
-      let cb_mousemove = Closure::<dyn FnMut(_)>::new(move |e: MouseEvent| {
+      let lock = Rc::new(DragLock::new(drag_cursor.unwrap_or("grabbing"), shield));
+
+      let cb_pointermove = Closure::<dyn FnMut(_)>::new({
+        let (active, x) = (active.clone(), x.clone());
+        move |e: PointerEvent| {
+
+          if active.get() != Some(e.pointer_id()) { return; }
 
-        unsafe {
           let x1 = if is_lateral { e.client_x() } else { e.client_y() } as f64;
-          if let Some(x0) = (*x).replace(x1) {
+          if let Some(x0) = x.replace(Some(x1)) {
             let delta = x1-x0;
 
-            if let Some(parent) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
+            if let Some(parent) = dom_node(bar_rf).map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
               
               let (w, sw) = if is_lateral {
                 (parent.client_width(), parent.scroll_width())
@@ -144,61 +198,153 @@ impl<G: GenericNode, T: 'static> ScrollBar<G, T> {
         }
       });
 
-      let cb_mousemove = Box::into_raw(Box::new(cb_mousemove));
+      // end of drag
+      let end_drag = Rc::new({
+        let (active, x, lock) = (active.clone(), x.clone(), lock.clone());
+        move || {
+          let Some(pointer_id) = active.take() else { return };
+          x.set(None);
+          lock.unlock();
+
+          if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<Element>()) {
+            if bar.has_pointer_capture(pointer_id) {
+              let _ = bar.release_pointer_capture(pointer_id);
+            }
+          }
+        }
+      });
 
-      let cb_mouseup: *mut Closure<dyn FnMut(MouseEvent)> = Box::into_raw(Box::new(Closure::<dyn FnMut(_)>::new(move |_: web_sys::MouseEvent| {})));
+      let cb_pointerup = Rc::new({
+        let (active, end_drag) = (active.clone(), end_drag.clone());
+        move |e: PointerEvent| {
+          if active.get() != Some(e.pointer_id()) { return; }
+          end_drag();
+        }
+      });
 
-      unsafe {
-        *cb_mouseup = Closure::<dyn FnMut(_)>::new(move |_: web_sys::MouseEvent| {
-          
-          let _ = (*x).take();
-          
-          let document = gloo_utils::document();
-          document.remove_event_listener_with_callback("mousemove", (*cb_mousemove).as_ref().unchecked_ref()).unwrap_throw();
-          document.remove_event_listener_with_callback("mouseup", (*cb_mouseup).as_ref().unchecked_ref()).unwrap_throw();
-        });
-      }
+      // Escape cancels the ongoing drag: restore scroll offset and end the drag
+      let cb_escape = Closure::<dyn FnMut(_)>::new({
+        let (active, end_drag, scroll0) = (active.clone(), end_drag.clone(), scroll0.clone());
+        move |e: KeyboardEvent| {
+          if e.key()!="Escape" || active.get().is_none() { return; }
+          e.prevent_default();
+
+          if let Some(parent) = dom_node(bar_rf).map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
+            if is_lateral {
+              parent.set_scroll_left(scroll0.get());
+            } else {
+              parent.set_scroll_top(scroll0.get());
+            }
+          }
+          end_drag();
+        }
+      });
+
+      // detaching ends the ongoing drag
+      listeners.on_detach(move || end_drag());
       
-      let cb_mousedown = Closure::<dyn FnMut(_)>::new(move |e: MouseEvent| {
-        unsafe {
-          let document = gloo_utils::document();
-          document.add_event_listener_with_callback("mousemove", (*cb_mousemove).as_ref().unchecked_ref()).unwrap_throw();
-          document.add_event_listener_with_callback("mouseup", (*cb_mouseup).as_ref().unchecked_ref()).unwrap_throw();
+      let cb_pointerdown = Closure::<dyn FnMut(_)>::new({
+        let (active, x, scroll0) = (active.clone(), x.clone(), scroll0.clone());
+        move |e: PointerEvent| {
+
+          // ignore other pointers while dragging, and non-primary buttons
+          if active.get().is_some() || e.button()!=0 { return; }
+          active.set(Some(e.pointer_id()));
+          lock.lock();
+
+          if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<Element>()) {
+            let _ = bar.set_pointer_capture(e.pointer_id());
+            if let Some(parent) = bar.parent_element() {
+              scroll0.set(if is_lateral { parent.scroll_left() } else { parent.scroll_top() });
+            }
+          }
         
           let x1 = if is_lateral { e.client_x() } else { e.client_y() } as f64;
-          let _ = (*x).replace(x1);
+          x.set(Some(x1));
         }
       });
 
-      // set listeners
-      if let Some(parent) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
-        parent.add_event_listener_with_callback("scroll", cb_scroll.as_ref().unchecked_ref()).unwrap_throw();
+      // set listeners (removed on clean-up, or by the handle)
+      if let Some(parent) = dom_node(bar_rf).map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
+        listeners.listen(&parent, "scroll", cb_scroll, false);
         if take_orthogonal {
-          parent.add_event_listener_with_callback_and_add_event_listener_options("wheel", cb_wheel.as_ref().unchecked_ref(), AddEventListenerOptions::new().passive(true)).unwrap_throw();
+          listeners.listen(&parent, "wheel", cb_wheel, true);
         }
-      }      
-      if let Some(bar) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<EventTarget>()) {
-        bar.add_event_listener_with_callback("mousedown", cb_mousedown.as_ref().unchecked_ref()).unwrap_throw();
       }
-
-      on_cleanup(move || {
-        if let Some(parent) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<Node>().parent_element()).flatten() {
-          parent.remove_event_listener_with_callback("scroll", cb_scroll.as_ref().unchecked_ref()).unwrap_throw();
-          if take_orthogonal {
-            parent.remove_event_listener_with_callback("wheel", cb_wheel.as_ref().unchecked_ref()).unwrap_throw();
-          }
-        }   
-        if let Some(bar) = bar_rf.try_get::<DomNode>().map(|x| x.unchecked_into::<EventTarget>()) {
-          bar.remove_event_listener_with_callback("mousedown", cb_mousedown.as_ref().unchecked_ref()).unwrap_throw();
+      if let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<EventTarget>()) {
+        listeners.listen(&bar, "pointerdown", cb_pointerdown, false);
+        listeners.listen(&bar, "pointermove", cb_pointermove, false);
+        listeners.listen(&gloo_utils::document(), "keydown", cb_escape, false);
+        for event in POINTER_END_EVENTS {
+          listeners.listen(&bar, event, Closure::<dyn FnMut(_)>::new({
+            let cb_pointerup = cb_pointerup.clone();
+            move |e: PointerEvent| cb_pointerup(e)
+          }), false);
         }
+      }
+    });
 
-        unsafe {
-          let _ = Box::from_raw(cb_mousemove);
-          let _ = Box::from_raw(cb_mouseup);
-          let _ = Box::from_raw(x);
+    ResizerHandle(listeners)
+  }
+
+  /// Observe sizes of parent and its children(except the bar), and changes of parent's children.
+  /// * On any change, update bar's style.
+  /// * Observers are owned by listeners: disconnected on detach (and clean-up), and connected again on reattach.
+  /// 
+  fn observe_parent(bar_rf: NodeRef<G>, is_lateral: bool, min_length: Option<StyleLength>, is_scrollable: Option<Signal<bool>>, listeners: &Listeners) {
+
+    let Some(bar) = dom_node(bar_rf).map(|x| x.unchecked_into::<HtmlElement>()) else { return };
+    let Some(parent) = bar.parent_element() else { return };
+
+    // (re)observe parent and its children
+    let observe = Rc::new({
+      let (bar, parent) = (bar.clone(), parent.clone());
+      move |observer: &ResizeObserver| {
+        observer.disconnect();
+        observer.observe(&parent);
+        let mut child = parent.first_element_child();
+        while let Some(element) = child {
+          child = element.next_element_sibling();
+          if element != *bar { observer.observe(&element); }
         }
-      });
+      }
+    });
+
+    // resize
+    let cb_resize = Closure::<dyn FnMut(_)>::new({
+      let bar = bar.clone();
+      move |_: JsValue| {
+        Self::update_bar_style(is_lateral, &bar, min_length, is_scrollable);
+      }
+    });
+    let resize_observer = ResizeObserver::new(cb_resize.as_ref().unchecked_ref()).unwrap_throw();
+
+    // children changes
+    let cb_mutation = Closure::<dyn FnMut(_)>::new({
+      let (resize_observer, observe) = (resize_observer.clone(), observe.clone());
+      move |_: JsValue| {
+        observe(&resize_observer);
+        Self::update_bar_style(is_lateral, &bar, min_length, is_scrollable);
+      }
     });
+    let mutation_observer = MutationObserver::new(cb_mutation.as_ref().unchecked_ref()).unwrap_throw();
+
+    let connect = {
+      let (resize_observer, mutation_observer) = (resize_observer.clone(), mutation_observer.clone());
+      move || {
+        observe(&resize_observer);
+        let options = MutationObserverInit::new();
+        options.set_child_list(true);
+        mutation_observer.observe_with_options(&parent, &options).unwrap_throw();
+      }
+    };
+    let disconnect = move || {
+      resize_observer.disconnect();
+      mutation_observer.disconnect();
+      // callbacks live as long as the observers
+      let _ = (&cb_resize, &cb_mutation);
+    };
+    listeners.observe(connect, disconnect);
   }
 
   fn update_scroll(elem: &Element, is_lateral: bool, delta: f64) {
@@ -259,7 +405,10 @@ pub struct ScrollBarProps<G: GenericNode, T: 'static> {
   is_scrolling: Option<Signal<bool>>,
   update_scrollbar: Option<ReadSignal<T>>,
   class: &'static str,
-  change_on_true: Option<(ReadSignal<bool>, Option<&'static str>, &'static str)>
+  change_on_true: Option<(ReadSignal<bool>, Option<&'static str>, &'static str)>,
+  enabled: Option<ReadSignal<bool>>,
+  drag_cursor: Option<&'static str>,
+  shield: Option<bool>,
 }
 
 /// ScrollBarComponent
@@ -275,6 +424,12 @@ pub struct ScrollBarProps<G: GenericNode, T: 'static> {
 /// * update_scrollbar: Option<ReadSignal<T>>,
 /// * class: &'static str,
 /// * change_on_true: Option<(ReadSignal<bool>, Option<&'static str>, &'static str)>
+/// * enabled: Option<ReadSignal<bool>>
+/// * drag_cursor: Option<&'static str>
+/// * shield: Option<bool>
+/// 
+/// The bar is rendered with the style of not-scrollable state({width/height}: 100%, {left/top}: 0%) and {"touch-action: none"} inline.
+/// So it has a length before mount (as in SSR), and hydration starts from the same style.
 #[component]
 pub fn ScrollBarComponent<G: Html, T: 'static>(props: ScrollBarProps<G, T>) -> View<G> {
 
@@ -288,14 +443,23 @@ pub fn ScrollBarComponent<G: Html, T: 'static>(props: ScrollBarProps<G, T>) -> V
     is_scrolling: props.is_scrolling,
     is_scrollable: props.is_scrollable,
     update_scrollbar: props.update_scrollbar,
+    enabled: props.enabled,
+    drag_cursor: props.drag_cursor,
+    shield: props.shield.unwrap_or(false),
   }.set_scrollbar();
 
   if let Some((bool_signal, old, new)) = props.change_on_true {
     ChangeClass::on_true(rf, bool_signal, old, new);
   }
 
+  let style = if props.is_lateral {
+    "width: 100.00%; left: 0.00%; touch-action: none;"
+  } else {
+    "height: 100.00%; top: 0.00%; touch-action: none;"
+  };
+
   view! {
-    div(ref=rf, class=props.class)
+    div(ref=rf, class=props.class, style=style)
   }
 }
 
@@ -331,13 +495,13 @@ pub fn sync_scroll_absolute_position<G: GenericNode, T: 'static>(
   synced: NodeRef<G>,
   lateral_scroll: bool,
   on_left: bool
-) -> () {
+) {
 
   on_mount(move || {
     create_effect(on(on_signal, move || {
 
-      if let Some(parent) = parent.try_get::<DomNode>().map(|x| x.unchecked_into::<Element>()) {
-        if let Some(elem) = synced.try_get::<DomNode>().map(|x| x.unchecked_into::<HtmlElement>()) {
+      if let Some(parent) = dom_node(parent).map(|x| x.unchecked_into::<Element>()) {
+        if let Some(elem) = dom_node(synced).map(|x| x.unchecked_into::<HtmlElement>()) {
           
           let mut to = (if lateral_scroll { parent.scroll_left() } else { parent.scroll_top() }) as f64;
           
@@ -0,0 +1,323 @@
+use crate::*;
+
+/// SplitNode
+/// * A node of `SplitLayout`'s tree description.
+///   * split node: split into `children` in the direction of `is_lateral`. (Look at `SplitNode::split`)
+///   * leaf node: no children. It renders the view given to `SplitLayout`. (Look at `SplitNode::leaf`)
+/// 
+/// # Fields
+/// * key: identifies the node. Should be unique in the tree.
+///   * Used as the parcel's dataset value (`data-split`) and as the key of `SplitLayout`'s sizes.
+/// * min_len: min limitation of the node's length in its parent split.
+/// * max_len: max limitation of the node's length in its parent split.
+/// * initial: initial length. Nodes without it share the rest(percent) evenly.
+///   * Pixel(or rem/em/vw/vh) initials mixed with shared nodes are converted into percent on mount,
+///     when the wrap's length is known, and the shared nodes take the rest of them.
+///     Until then (e.g. in the server's html), they're rendered as they are.
+/// * is_lateral: (split node) are children laid in lateral direction or vertical direction?
+/// * children: (split node) child nodes.
+/// 
+/// # Examples
+/// ```
+/// use sycamore_seoul::{SplitNode, StyleLength};
+/// let _ = SplitNode::split("root", true, vec![
+///   SplitNode::leaf("nav").with_initial(StyleLength::Percent(20.)).with_min_len(StyleLength::Pixel(100.)),
+///   SplitNode::split("main", false, vec![
+///     SplitNode::leaf("editor"),
+///     SplitNode::leaf("terminal").with_initial(StyleLength::Percent(30.)),
+///   ]),
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitNode {
+  pub key: String,
+  pub min_len: Option<StyleLength>,
+  pub max_len: Option<StyleLength>,
+  pub initial: Option<StyleLength>,
+  pub is_lateral: bool,
+  pub children: Vec<SplitNode>,
+}
+
+impl SplitNode {
+
+  pub fn leaf(key: &str) -> Self {
+    Self { key: key.to_string(), min_len: None, max_len: None, initial: None, is_lateral: true, children: Vec::new() }
+  }
+
+  pub fn split(key: &str, is_lateral: bool, children: Vec<SplitNode>) -> Self {
+    Self { is_lateral, children, ..Self::leaf(key) }
+  }
+
+  pub fn with_min_len(self, min_len: StyleLength) -> Self {
+    Self { min_len: Some(min_len), ..self }
+  }
+
+  pub fn with_max_len(self, max_len: StyleLength) -> Self {
+    Self { max_len: Some(max_len), ..self }
+  }
+
+  pub fn with_initial(self, initial: StyleLength) -> Self {
+    Self { initial: Some(initial), ..self }
+  }
+
+  pub fn is_leaf(&self) -> bool {
+    self.children.is_empty()
+  }
+}
+
+
+/// CSS needed by `SplitLayout`
+/// * Override classes of `split-` prefix to customize. (e.g. `.split-resizer-moving` while resizing)
+/// 
+pub const SPLIT_CSS: &str = "\
+.split-root { width: 100%; height: 100%; }
+.split-wrap { display: flex; width: 100%; height: 100%; overflow: hidden; }
+.split-wrap-y { flex-direction: column; }
+.split-parcel { position: relative; flex: 1 1 auto; min-width: 0; min-height: 0; }
+.split-content { width: 100%; height: 100%; overflow: hidden; }
+.split-resizer { position: absolute; z-index: 10; background-color: transparent; }
+.split-resizer:hover, .split-resizer:focus-visible, .split-resizer-moving { background-color: rgba(10, 120, 250, 0.5); outline: none; }
+.split-resizer-x { top: 0; right: -3px; width: 6px; height: 100%; cursor: col-resize; }
+.split-resizer-y { left: 0; bottom: -3px; width: 100%; height: 6px; cursor: row-resize; }
+";
+
+
+#[derive(Props)]
+pub struct SplitLayoutProps<G: Html, F>
+where F: Fn(&str) -> View<G> + 'static
+{
+  tree: SplitNode,
+  view: F,
+  sizes: Option<Signal<HashMap<String, StyleLength>>>,
+}
+
+/// SplitLayout
+/// * Declarative nested layout built on parcels type resizer.
+/// * Generates wrap/parcel/resizer structure from the tree description(`SplitNode`), and the CSS it needs(`SPLIT_CSS`).
+/// * Each parcel gets `data-split` dataset of its node's key, and each wrap's parcels (except the last one) get `ParcelsResizer`.
+/// * Node's min_len/max_len go to the parcel's `data-min-len`/`data-max-len`, so the resizers honour each node's own limitations.
+/// 
+/// # Props
+/// * tree: SplitNode,
+/// * view: F (Fn(&str) -> View<G>): view of each leaf node, by its key.
+/// * sizes: Option<Signal<HashMap<String, StyleLength>>>: sizes of the whole tree's nodes, keyed by node's key.
+/// 
+/// The layout fills its parent element (width/height 100%).
+/// 
+#[component]
+pub fn SplitLayout<G: Html, F>(props: SplitLayoutProps<G, F>) -> View<G>
+where F: Fn(&str) -> View<G> + 'static
+{
+  let sizes = props.sizes.unwrap_or(create_signal(HashMap::new()));
+  let view: Rc<dyn Fn(&str) -> View<G>> = Rc::new(props.view);
+
+  // initial sizes
+  let mut initials = HashMap::new();
+  collect_initial_lengths(&props.tree, &mut initials);
+  sizes.update(|x| x.extend(initials.clone()));
+
+  let key = props.tree.key.clone();
+  let content = split_content(&props.tree, &view, &initials, sizes);
+
+  view! {
+    style { (SPLIT_CSS) }
+    div(class="split-root", data-split=key) {
+      (content)
+    }
+  }
+}
+
+
+/// Initial lengths of children: given ones, or even share of the rest(percent)
+/// * Fr lengths share the rest by their fractions, and children without initial length take 1fr each.
+/// * measured: (wrap's length, its context). Given, absolute initials are converted into percent and taken out of the rest.
+///   Otherwise they're kept as they are, and not subtracted from the rest.
+/// 
+fn initial_lengths(children: &[SplitNode], measured: Option<(f64, &LengthContext)>) -> Vec<StyleLength> {
+  let absolute = |initial: Option<StyleLength>| match (initial, measured) {
+    (Some(x @ (StyleLength::Pixel(_) | StyleLength::Rem(_) | StyleLength::Em(_) | StyleLength::Vw(_) | StyleLength::Vh(_))), Some((par_len, ctx)))
+      if par_len>0. => Some(x.to_percent_with(par_len, ctx)),
+    _ => None
+  };
+  let given: f64 = children.iter().filter_map(|x| match x.initial {
+    Some(StyleLength::Percent(v)) | Some(StyleLength::PixelPercent(_, v)) => Some(v),
+    initial => absolute(initial)
+  }).sum();
+  let fr_total: f64 = children.iter().filter_map(|x| match x.initial {
+    Some(StyleLength::Fr(v)) => Some(v),
+    None => Some(1.),
+    _ => None
+  }).sum();
+  let share = if fr_total>0. { (100.-given).max(0.)/fr_total } else { 0. };
+
+  children.iter().map(|x| match x.initial {
+    Some(StyleLength::Fr(v)) => StyleLength::Percent(v*share),
+    initial => absolute(initial).map(StyleLength::Percent).or(initial).unwrap_or(StyleLength::Percent(share)),
+  }).collect()
+}
+
+/// Do the children need the wrap's length for their initial lengths?
+/// * true if an absolute initial is mixed with nodes sharing the rest.
+/// 
+fn needs_measure(children: &[SplitNode]) -> bool {
+  let is_absolute = |x: &SplitNode| matches!(x.initial,
+    Some(StyleLength::Pixel(_) | StyleLength::Rem(_) | StyleLength::Em(_) | StyleLength::Vw(_) | StyleLength::Vh(_)));
+  let is_shared = |x: &SplitNode| matches!(x.initial, None | Some(StyleLength::Fr(_)));
+  children.iter().any(is_absolute) && children.iter().any(is_shared)
+}
+
+/// Collect initial lengths of all nodes in the tree
+/// 
+fn collect_initial_lengths(node: &SplitNode, map: &mut HashMap<String, StyleLength>) {
+  for (child, initial) in node.children.iter().zip(initial_lengths(&node.children, None)) {
+    map.insert(child.key.clone(), initial);
+    collect_initial_lengths(child, map);
+  }
+}
+
+/// Inline style of parcel: length and its limitations
+/// 
+fn parcel_style(node: &SplitNode, initial: StyleLength, is_lateral: bool) -> String {
+  let (len, min, max) = if is_lateral { ("width", "min-width", "max-width") } else { ("height", "min-height", "max-height") };
+
+  [(len, Some(initial)), (min, node.min_len), (max, node.max_len)].into_iter()
+    .filter_map(|(property, value)| {
+      let value = value?.style_value();
+      if value.is_empty() { None } else { Some(format!("{property}: {value};")) }
+    })
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Build view of the node's content
+/// * leaf node: given view
+/// * split node: wrap of parcels
+/// 
+fn split_content<G: Html>(
+  node: &SplitNode,
+  view: &Rc<dyn Fn(&str) -> View<G>>,
+  initials: &HashMap<String, StyleLength>,
+  sizes: Signal<HashMap<String, StyleLength>>
+) -> View<G> {
+
+  if node.is_leaf() {
+    return view(&node.key);
+  }
+
+  let is_lateral = node.is_lateral;
+  let (wrap_class, resizer_class) = if is_lateral {
+    ("split-wrap", "split-resizer split-resizer-x")
+  } else {
+    ("split-wrap split-wrap-y", "split-resizer split-resizer-y")
+  };
+  let last = node.children.len()-1;
+
+  // convert absolute initials on mount. (Before the children's, whose wraps follow this one)
+  let wrap_rf = create_node_ref();
+  if needs_measure(&node.children) {
+    let children = node.children.clone();
+    on_mount(move || {
+      let Some(wrap) = dom_node(wrap_rf).map(|x| x.unchecked_into::<Element>()) else { return };
+      let par_len = scroll_length(&wrap, is_lateral);
+      if par_len<=0. { return; }
+      let ctx = LengthContext::measure(Some(&wrap));
+      let lengths = initial_lengths(&children, Some((par_len, &ctx)));
+
+      let parcels = wrap.children();
+      for i in 0..parcels.length() {
+        let Some(parcel) = parcels.item(i) else { continue };
+        let Some(key) = parcel.get_attribute("data-split") else { continue };
+        let Some(style_len) = children.iter().position(|x| x.key==key).map(|i| lengths[i]) else { continue };
+        style_len.set_style(parcel.unchecked_ref(), is_lateral);
+      }
+      sizes.update(|x| x.extend(children.iter().map(|x| x.key.clone()).zip(lengths)));
+    });
+  }
+
+  let parcels = node.children.iter().enumerate().map(|(i, child)| {
+
+    let key = child.key.clone();
+    let min_len = child.min_len.unwrap_or_default().to_string();
+    let max_len = child.max_len.unwrap_or_default().to_string();
+    let style = parcel_style(child, initials.get(&child.key).copied().unwrap_or_default(), is_lateral);
+    let content = split_content(child, view, initials, sizes);
+
+    let resizer = if i<last {
+      view! {
+        ParcelsResizer(
+          parcel_lengths=sizes,
+          parcel_name="split",
+          class=resizer_class,
+          change_class_on_move=(None, "split-resizer-moving"),
+          is_lateral=is_lateral,
+          to_left=false,
+          to_pixel=false,
+        )
+      }
+    } else {
+      view! { }
+    };
+
+    view! {
+      div(class="split-parcel", style=style, data-split=key, data-min-len=min_len, data-max-len=max_len) {
+        (resizer)
+        div(class="split-content") {
+          (content)
+        }
+      }
+    }
+  }).collect::<Vec<_>>();
+
+  let parcels = View::new_fragment(parcels);
+
+  view! {
+    div(ref=wrap_rf, class=wrap_class) {
+      (parcels)
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn children(initials: &[Option<StyleLength>]) -> Vec<SplitNode> {
+    initials.iter().enumerate().map(|(i, initial)| SplitNode { initial: *initial, ..SplitNode::leaf(&i.to_string()) }).collect()
+  }
+
+  #[test]
+  fn percent_and_fr_share_the_rest() {
+    let children = children(&[Some(StyleLength::Percent(40.)), Some(StyleLength::Fr(2.)), None]);
+    assert!(!needs_measure(&children));
+    assert_eq!(initial_lengths(&children, None), vec![StyleLength::Percent(40.), StyleLength::Percent(40.), StyleLength::Percent(20.)]);
+  }
+
+  #[test]
+  fn mixed_pixel_percent_and_none() {
+    let children = children(&[Some(StyleLength::Pixel(200.)), Some(StyleLength::Percent(30.)), None]);
+    assert!(needs_measure(&children));
+
+    // before mount: absolute initial is kept, and not taken out of the rest
+    assert_eq!(initial_lengths(&children, None), vec![StyleLength::Pixel(200.), StyleLength::Percent(30.), StyleLength::Percent(70.)]);
+
+    // measured: it's converted into percent, and the rest shrinks
+    let ctx = LengthContext::default();
+    assert_eq!(initial_lengths(&children, Some((1000., &ctx))), vec![StyleLength::Percent(20.), StyleLength::Percent(30.), StyleLength::Percent(50.)]);
+  }
+
+  #[test]
+  fn relative_units_are_measured_with_context() {
+    let children = children(&[Some(StyleLength::Rem(10.)), Some(StyleLength::Vw(10.)), None, None]);
+    let ctx = LengthContext { root_font_size: 10., viewport_width: 2000., ..LengthContext::default() };
+    assert_eq!(initial_lengths(&children, Some((1000., &ctx))),
+      vec![StyleLength::Percent(10.), StyleLength::Percent(20.), StyleLength::Percent(35.), StyleLength::Percent(35.)]);
+  }
+
+  #[test]
+  fn absolute_only_split_is_kept() {
+    let children = children(&[Some(StyleLength::Pixel(200.)), Some(StyleLength::Percent(30.))]);
+    assert!(!needs_measure(&children));
+    assert_eq!(initial_lengths(&children, None), vec![StyleLength::Pixel(200.), StyleLength::Percent(30.)]);
+  }
+}
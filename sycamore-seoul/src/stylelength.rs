@@ -1,19 +1,67 @@
 use crate::*;
 
-/// StyleLength handles css length in either/both pixel or percent format.
+/// StyleLength handles css length in pixel, percent, or font/viewport relative units.
 /// 
 /// # Examples
 /// ```
+/// use sycamore_seoul::StyleLength;
 /// let _ = StyleLength::Pixel(20.);
 /// let _ = StyleLength::Percent(80.);
 /// let _ = StyleLength::PixelPercent(20., 20.); 
+/// let _ = StyleLength::Rem(12.);
 /// ```
-#[derive(Default, Debug, Clone, Copy)]
+/// 
+/// # Units
+/// * Pixel, Percent(of the wrapping element), PixelPercent(both)
+/// * Rem, Em: font relative. Vw, Vh: viewport relative.
+/// * Fr: fraction of the wrapping element's length, shared by `LengthContext`'s `fr_total`. (`Fr(1.)` is the whole length by default.)
+/// 
+/// Relative units are converted into pixel through `LengthContext`.
+/// * `min_check`, `max_check`, `to_percent`, `to_pixel` and `set_style` measure the context at every call,
+///   so limitations like `Rem(12.)` follow the current root font size.
+///   (`Em` is resolved against the root element there. Use `*_with` methods with `LengthContext::measure(Some(element))` for the element's own font size.)
+/// * `set_style` writes rem, em, vw and vh as they are, so the browser resolves them. Fr is written in percent.
+/// 
+/// # Text format
+/// StyleLength implements `FromStr` and `Display` with the following grammar, which round-trips every variant.
+/// 
+/// | variant                    | text         |
+/// |----------------------------|--------------|
+/// | `Null`                     | `"null"`     |
+/// | `Pixel(20.)`               | `"20px"`     |
+/// | `Percent(35.)`             | `"35%"`      |
+/// | `PixelPercent(20., 35.)`   | `"20px\|35%"` |
+/// | `Rem(12.)`                 | `"12rem"`    |
+/// | `Em(2.)`                   | `"2em"`      |
+/// | `Vw(50.)`                  | `"50vw"`     |
+/// | `Vh(50.)`                  | `"50vh"`     |
+/// | `Fr(1.)`                   | `"1fr"`      |
+/// 
+/// * Numbers are written in Rust's `f64` format, e.g. `"12.5px"`, `"-3%"`, `"1e2px"`.
+/// * On parsing, whitespace around each part is ignored, and an empty string is also `Null`.
+/// 
+/// ```
+/// use sycamore_seoul::StyleLength;
+/// let len: StyleLength = "20px|35%".parse().unwrap();
+/// assert_eq!(len, StyleLength::PixelPercent(20., 35.));
+/// assert_eq!(len.to_string(), "20px|35%");
+/// assert_eq!("12rem".parse::<StyleLength>().unwrap(), StyleLength::Rem(12.));
+/// ```
+/// 
+/// # Serde
+/// With the `serde` feature, StyleLength is (de)serialized as a string of the text format.
+/// 
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
 pub enum StyleLength {
   #[default] Null,
   Pixel(f64),
   Percent(f64),
   PixelPercent(f64, f64),
+  Rem(f64),
+  Em(f64),
+  Vw(f64),
+  Vh(f64),
+  Fr(f64),
 }
 
 impl StyleLength {
@@ -26,85 +74,390 @@ impl StyleLength {
     }
   }
 
+  /// Measure element's length from its bounding rect
+  /// * Percent is of the parent element's (scroll) length.
+  /// * Return None if the element has no parent.
+  /// 
+  pub fn from_rect(element: &Element, is_lateral: bool, to_pixel: bool) -> Option<Self> {
+    let par_len = parent_length(element, is_lateral)?;
+    let rect = element.get_bounding_client_rect();
+    Some(Self::new(if is_lateral { rect.width() } else { rect.height() }, par_len, to_pixel))
+  }
+
+  /// Measure element's length from its computed style (width or height)
+  /// * Unlike the bounding rect, it's not affected by css transform.
+  /// * Return None if the element has no parent, or the computed value is not in pixel (e.g. `auto` of a hidden element).
+  /// 
+  pub fn from_computed_style(element: &Element, is_lateral: bool, to_pixel: bool) -> Option<Self> {
+    let par_len = parent_length(element, is_lateral)?;
+    let len = web_sys::window()?.get_computed_style(element).ok()??
+      .get_property_value(if is_lateral { "width" } else { "height" }).ok()?
+      .trim().strip_suffix("px")?.parse::<f64>().ok()?;
+    Some(Self::new(len, par_len, to_pixel))
+  }
+
   /// Is given lengths are bigger than self? (min-limitation check)
   /// 
   pub fn min_check(&self, len: f64, par_len: f64) -> bool {
+    self.min_check_with(len, par_len, &self.context())
+  }
+
+  /// `min_check` with given context
+  /// 
+  pub fn min_check_with(&self, len: f64, par_len: f64, ctx: &LengthContext) -> bool {
     match self {
+      Self::Null => true,
       Self::Pixel(v) => len>=*v,
       Self::Percent(v) => len/par_len*100.>=*v,
       Self::PixelPercent(v1, v2) => len>=*v1 && len/par_len*100.>=*v2,
-      _ => true,
+      _ => len>=self.to_pixel_with(par_len, ctx),
     }
   }
 
   /// Is given lengths are smaller than self? (max-limitation check)
   /// 
   pub fn max_check(&self, len: f64, par_len: f64) -> bool {
+    self.max_check_with(len, par_len, &self.context())
+  }
+
+  /// `max_check` with given context
+  /// 
+  pub fn max_check_with(&self, len: f64, par_len: f64, ctx: &LengthContext) -> bool {
     match self {
+      Self::Null => true,
       Self::Pixel(v) => len<=*v,
       Self::Percent(v) => len/par_len*100.<=*v,
       Self::PixelPercent(v1, v2) => len<=*v1 && len/par_len*100.<=*v2,
-      _ => true,
+      _ => len<=self.to_pixel_with(par_len, ctx),
     }
   }
 
   /// Change self into percent length
   /// 
   pub fn to_percent(&self, parent_len: f64) -> f64 {
+    self.to_percent_with(parent_len, &self.context())
+  }
+
+  /// `to_percent` with given context
+  /// 
+  pub fn to_percent_with(&self, parent_len: f64, ctx: &LengthContext) -> f64 {
     match self {
-      Self::Pixel(v) => (*v)/parent_len*100.,
+      Self::Null => 0.,
       Self::Percent(v) => *v,
       Self::PixelPercent(_, v) => *v,
-      _ => 0.
+      Self::Fr(v) => (*v)/ctx.fr_total*100.,
+      _ => self.to_pixel_with(parent_len, ctx)/parent_len*100.,
     }
   }
 
-  fn pixel(&self) -> Option<f64> {
+  /// Change self into pixel length
+  /// 
+  pub fn to_pixel(&self, parent_len: f64) -> f64 {
+    self.to_pixel_with(parent_len, &self.context())
+  }
+
+  /// `to_pixel` with given context
+  /// 
+  pub fn to_pixel_with(&self, parent_len: f64, ctx: &LengthContext) -> f64 {
     match self {
-      Self::Pixel(v) => Some(*v),
-      Self::Percent(_) => None,
-      Self::PixelPercent(v, _) => Some(*v),
-      _ => None,
+      Self::Null => 0.,
+      Self::Pixel(v) => *v,
+      Self::Percent(v) => (*v)*parent_len/100.,
+      Self::PixelPercent(v, _) => *v,
+      Self::Rem(v) => (*v)*ctx.root_font_size,
+      Self::Em(v) => (*v)*ctx.font_size,
+      Self::Vw(v) => (*v)*ctx.viewport_width/100.,
+      Self::Vh(v) => (*v)*ctx.viewport_height/100.,
+      Self::Fr(v) => (*v)/ctx.fr_total*parent_len,
     }
   }
 
-  fn percent(&self) -> Option<f64> {
+  /// Context to convert self: measured only for font/viewport relative units
+  /// 
+  fn context(&self) -> LengthContext {
     match self {
-      Self::Pixel(_) => None,
-      Self::Percent(v) => Some(*v),
-      Self::PixelPercent(_, v) => Some(*v),
-      _ => None,
+      Self::Rem(_) | Self::Em(_) | Self::Vw(_) | Self::Vh(_) => LengthContext::measure(None),
+      _ => LengthContext::default(),
     }
   }
 
   pub fn value(&self) -> f64 {
     match self {
-      Self::Pixel(v) => *v,
-      Self::Percent(v) => *v,
-      Self::PixelPercent(v, _) => *v,
-      _ => 0.
+      Self::Null => 0.,
+      Self::Pixel(v) | Self::Percent(v) | Self::PixelPercent(v, _) => *v,
+      Self::Rem(v) | Self::Em(v) | Self::Vw(v) | Self::Vh(v) | Self::Fr(v) => *v,
     }
   }
 
   pub fn style_value(&self) -> String {
-    if let Some(pixel) = self.pixel() {
-      format!("{:.2}px", pixel)
-    } else if let Some(percent) = self.percent() {
-      format!("{:.2}%", percent)
-    } else {
+    self.style_value_with(&LengthContext::default())
+  }
+
+  /// `style_value` with given context (Fr is written in percent of `fr_total`)
+  /// 
+  pub fn style_value_with(&self, ctx: &LengthContext) -> String {
+    match self {
+      Self::Null => String::new(),
+      Self::Pixel(v) | Self::PixelPercent(v, _) => format!("{:.2}px", v),
+      Self::Percent(v) => format!("{:.2}%", v),
+      Self::Rem(v) => format!("{:.2}rem", v),
+      Self::Em(v) => format!("{:.2}em", v),
+      Self::Vw(v) => format!("{:.2}vw", v),
+      Self::Vh(v) => format!("{:.2}vh", v),
+      Self::Fr(v) => format!("{:.2}%", v/ctx.fr_total*100.),
+    }
+  }
+
+  /// Inline style declaration of the length, as `set_style` would write it. e.g. "width: 30.00%;"
+  /// * Empty for Null.
+  /// * Use it in `style` attribute to render lengths without DOM, as in SSR.
+  /// 
+  /// ```
+  /// use sycamore_seoul::StyleLength;
+  /// assert_eq!(StyleLength::Percent(30.).inline_style(true), "width: 30.00%;");
+  /// assert_eq!(StyleLength::Null.inline_style(false), "");
+  /// ```
+  pub fn inline_style(&self, is_lateral: bool) -> String {
+    if let Self::Null = self {
       String::new()
+    } else {
+      format!("{}: {};", if is_lateral { "width" } else { "height" }, self.style_value())
     }
   }
 
   /// Set style of lengths
   /// 
   pub fn set_style(&self, element: &HtmlElement, is_lateral: bool) {
+    self.set_style_with(element, is_lateral, &LengthContext::default())
+  }
+
+  /// `set_style` with given context
+  /// 
+  pub fn set_style_with(&self, element: &HtmlElement, is_lateral: bool, ctx: &LengthContext) {
 
     if let Self::Null = self {
       //
     } else {
       let property = if is_lateral { "width" } else { "height" };
-      element.style().set_property(property, &self.style_value()).unwrap_throw();
+      element.style().set_property(property, &self.style_value_with(ctx)).unwrap_throw();
+    }
+  }
+}
+
+
+/// LengthContext
+/// * Resolves font/viewport relative units of `StyleLength` into pixel.
+/// 
+/// # Fields
+/// * root_font_size: font size(px) of the root element, for rem.
+/// * font_size: font size(px) of the element, for em.
+/// * viewport_width, viewport_height: viewport size(px), for vw and vh.
+/// * fr_total: sum of fr values sharing the wrapping element's length. Default is 1.
+/// 
+/// `default()` is the browser's default (16px font, no viewport). `measure()` reads the current document.
+/// 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthContext {
+  pub root_font_size: f64,
+  pub font_size: f64,
+  pub viewport_width: f64,
+  pub viewport_height: f64,
+  pub fr_total: f64,
+}
+
+impl Default for LengthContext {
+  fn default() -> Self {
+    Self { root_font_size: DEFAULT_FONT_SIZE, font_size: DEFAULT_FONT_SIZE, viewport_width: 0., viewport_height: 0., fr_total: 1. }
+  }
+}
+
+impl LengthContext {
+
+  /// Measure the context from the current document
+  /// * element: font size of em is read from it if given. (Otherwise from the root element)
+  /// * Without window (e.g. on the server), it's the default.
+  /// 
+  pub fn measure(element: Option<&Element>) -> Self {
+    let Some(window) = web_sys::window() else { return Self::default() };
+    let root = window.document().and_then(|x| x.document_element());
+
+    let font_size = |element: Option<&Element>| element
+      .and_then(|x| window.get_computed_style(x).ok().flatten())
+      .and_then(|x| x.get_property_value("font-size").ok())
+      .and_then(|x| x.trim().strip_suffix("px").and_then(|x| x.parse::<f64>().ok()))
+      .unwrap_or(DEFAULT_FONT_SIZE);
+
+    let root_font_size = font_size(root.as_ref());
+    let viewport = |x: Result<JsValue, JsValue>| x.ok().and_then(|x| x.as_f64()).unwrap_or(0.);
+
+    Self {
+      root_font_size,
+      font_size: element.map(|x| font_size(Some(x))).unwrap_or(root_font_size),
+      viewport_width: viewport(window.inner_width()),
+      viewport_height: viewport(window.inner_height()),
+      fr_total: 1.,
+    }
+  }
+}
+
+const DEFAULT_FONT_SIZE: f64 = 16.;
+
+/// Parent element's scroll length
+/// 
+fn parent_length(element: &Element, is_lateral: bool) -> Option<f64> {
+  let parent = element.parent_element()?;
+  Some(if is_lateral { parent.scroll_width() } else { parent.scroll_height() } as f64)
+}
+
+
+impl std::fmt::Display for StyleLength {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Null => write!(f, "null"),
+      Self::Pixel(v) => write!(f, "{v}px"),
+      Self::Percent(v) => write!(f, "{v}%"),
+      Self::PixelPercent(v1, v2) => write!(f, "{v1}px|{v2}%"),
+      Self::Rem(v) => write!(f, "{v}rem"),
+      Self::Em(v) => write!(f, "{v}em"),
+      Self::Vw(v) => write!(f, "{v}vw"),
+      Self::Vh(v) => write!(f, "{v}vh"),
+      Self::Fr(v) => write!(f, "{v}fr"),
     }
   }
-}
\ No newline at end of file
+}
+
+impl FromStr for StyleLength {
+  type Err = ParseStyleLengthError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let err = || ParseStyleLengthError(s.to_string());
+
+    let value = s.trim();
+    if value.is_empty() || value=="null" {
+      return Ok(Self::Null);
+    }
+
+    let number = |x: &str, unit: &str| x.trim().strip_suffix(unit).and_then(|x| x.trim().parse::<f64>().ok());
+    let pixel = |x: &str| number(x, "px");
+    let percent = |x: &str| number(x, "%");
+
+    if let Some((v1, v2)) = value.split_once('|') {
+      return pixel(v1).zip(percent(v2)).map(|(v1, v2)| Self::PixelPercent(v1, v2)).ok_or_else(err);
+    }
+
+    // "rem" goes before "em"
+    let units: [(&str, fn(f64) -> Self); 7] = [
+      ("px", Self::Pixel), ("%", Self::Percent), ("rem", Self::Rem), ("em", Self::Em),
+      ("vw", Self::Vw), ("vh", Self::Vh), ("fr", Self::Fr),
+    ];
+    units.into_iter().find_map(|(unit, f)| number(value, unit).map(f)).ok_or_else(err)
+  }
+}
+
+/// Error of parsing StyleLength from text
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStyleLengthError(String);
+
+impl std::fmt::Display for ParseStyleLengthError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "invalid style length: {:?}", self.0)
+  }
+}
+
+impl std::error::Error for ParseStyleLengthError {}
+
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for StyleLength {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StyleLength {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    <String as serde::Deserialize>::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    let lengths = [
+      StyleLength::Null,
+      StyleLength::Pixel(20.),
+      StyleLength::Percent(35.5),
+      StyleLength::PixelPercent(20., 35.),
+      StyleLength::Rem(12.),
+      StyleLength::Em(2.5),
+      StyleLength::Vw(50.),
+      StyleLength::Vh(-3.),
+      StyleLength::Fr(1.),
+    ];
+    for len in lengths {
+      assert_eq!(len.to_string().parse::<StyleLength>(), Ok(len), "{len}");
+    }
+  }
+
+  #[test]
+  fn parse_units() {
+    assert_eq!("12rem".parse(), Ok(StyleLength::Rem(12.)));
+    assert_eq!("12em".parse(), Ok(StyleLength::Em(12.)));
+    assert_eq!(" 1.5 fr ".parse(), Ok(StyleLength::Fr(1.5)));
+    assert_eq!("1e2vw".parse(), Ok(StyleLength::Vw(100.)));
+    assert_eq!("20px | 35%".parse(), Ok(StyleLength::PixelPercent(20., 35.)));
+    assert_eq!("".parse(), Ok(StyleLength::Null));
+  }
+
+  #[test]
+  fn parse_errors() {
+    for text in ["12", "rem", "12r", "12rm", "calc(100% - 20px)", "auto", "20%|35px"] {
+      assert!(text.parse::<StyleLength>().is_err(), "{text}");
+    }
+  }
+
+  #[test]
+  fn display_format() {
+    assert_eq!(StyleLength::Null.to_string(), "null");
+    assert_eq!(StyleLength::Pixel(12.5).to_string(), "12.5px");
+    assert_eq!(StyleLength::Percent(-3.).to_string(), "-3%");
+    assert_eq!(StyleLength::PixelPercent(20., 35.).to_string(), "20px|35%");
+    assert_eq!(StyleLength::Rem(12.).to_string(), "12rem");
+    assert_eq!(StyleLength::Em(2.).to_string(), "2em");
+    assert_eq!(StyleLength::Vw(50.).to_string(), "50vw");
+    assert_eq!(StyleLength::Vh(50.).to_string(), "50vh");
+    assert_eq!(StyleLength::Fr(1.).to_string(), "1fr");
+  }
+
+  #[test]
+  fn parse_error_keeps_text() {
+    let err = "20pt".parse::<StyleLength>().unwrap_err();
+    assert_eq!(err, ParseStyleLengthError("20pt".to_string()));
+    assert_eq!(err.to_string(), "invalid style length: \"20pt\"");
+    assert!("20px|".parse::<StyleLength>().is_err());
+    assert!("|35%".parse::<StyleLength>().is_err());
+    assert!("nullpx".parse::<StyleLength>().is_err());
+  }
+
+  #[test]
+  fn convert_with_context() {
+    let ctx = LengthContext { root_font_size: 10., font_size: 20., viewport_width: 1000., viewport_height: 500., fr_total: 4. };
+    assert_eq!(StyleLength::Rem(2.).to_pixel_with(200., &ctx), 20.);
+    assert_eq!(StyleLength::Em(2.).to_pixel_with(200., &ctx), 40.);
+    assert_eq!(StyleLength::Vw(10.).to_pixel_with(200., &ctx), 100.);
+    assert_eq!(StyleLength::Vh(10.).to_pixel_with(200., &ctx), 50.);
+    assert_eq!(StyleLength::Fr(1.).to_pixel_with(200., &ctx), 50.);
+    assert_eq!(StyleLength::Fr(1.).to_percent_with(200., &ctx), 25.);
+    assert_eq!(StyleLength::Fr(1.).style_value_with(&ctx), "25.00%");
+    assert!(StyleLength::Rem(2.).min_check_with(20., 200., &ctx));
+    assert!(!StyleLength::Rem(2.).min_check_with(19., 200., &ctx));
+    assert!(StyleLength::Em(2.).max_check_with(40., 200., &ctx));
+    assert!(!StyleLength::Em(2.).max_check_with(41., 200., &ctx));
+  }
+}
@@ -0,0 +1,107 @@
+use sycamore::prelude::*;
+use sycamore_seoul::*;
+use hashbrown::HashMap;
+
+
+#[test]
+fn panel_resizer_renders_separator() {
+  let html = sycamore::render_to_string(|| view! {
+    PanelResizer(class="resizer", is_lateral=true, to_left=false, to_pixel=false)
+  });
+
+  assert!(html.contains(r#"class="resizer""#));
+  assert!(html.contains(r#"role="separator""#));
+  assert!(html.contains(r#"aria-orientation="vertical""#));
+  assert!(html.contains("touch-action: none;"));
+}
+
+#[test]
+fn panel_initial_length_is_inlined() {
+  let html = sycamore::render_to_string(|| {
+    let panel_length = create_signal(StyleLength::Pixel(240.));
+    view! {
+      div(class="panel", style=panel_length.get().inline_style(true)) {
+        PanelResizer(panel_length=panel_length, class="resizer", is_lateral=true, to_left=false, to_pixel=true)
+      }
+    }
+  });
+
+  assert!(html.contains("width: 240.00px;"));
+}
+
+#[test]
+fn panel_persisted_length_is_inlined() {
+  let storage = MemoryStorage::new();
+  let persist = Persist::new("panel", storage.clone());
+  persist.save(StyleLength::Percent(30.));
+
+  let html = sycamore::render_to_string(move || {
+    let panel_length = create_signal(StyleLength::Null);
+    view! {
+      div(class="panel", style=panel_length.get().inline_style(false)) {
+        PanelResizer(panel_length=panel_length, persist=persist, class="resizer", is_lateral=false, to_left=false, to_pixel=false)
+      }
+    }
+  });
+
+  assert!(html.contains("height: 30.00%;"));
+  assert!(html.contains(r#"aria-orientation="horizontal""#));
+}
+
+#[test]
+fn parcels_persisted_lengths_are_inlined() {
+  let storage = MemoryStorage::new();
+  let persist = Persist::new("parcels", storage.clone());
+  persist.save_parcels([("a".to_string(), StyleLength::Percent(40.)), ("b".to_string(), StyleLength::Percent(60.))]);
+
+  let html = sycamore::render_to_string(move || {
+    let mut given = HashMap::new();
+    given.insert("a".to_string(), StyleLength::Percent(25.));
+    let parcel_lengths = create_signal(given);
+    let style = move |key: &str| parcel_lengths.with(|map| map.get(key).copied().unwrap_or_default().inline_style(true));
+
+    view! {
+      div(class="wrap") {
+        div(data-parcel="a", style=style("a")) {
+          ParcelsResizer::<_, String>(parcel_lengths=parcel_lengths, parcel_name="parcel", persist=persist, class="resizer", is_lateral=true, to_left=false, to_pixel=false)
+        }
+        div(data-parcel="b", style=style("b"))
+      }
+    }
+  });
+
+  // given lengths are kept, and missing ones are taken from persisted
+  assert!(html.contains("width: 25.00%;"));
+  assert!(html.contains("width: 60.00%;"));
+  assert!(!html.contains("width: 40.00%;"));
+}
+
+#[test]
+fn local_storage_is_empty_on_server() {
+  let html = sycamore::render_to_string(|| {
+    let panel_length = create_signal(StyleLength::Null);
+    view! {
+      div(class="panel", style=panel_length.get().inline_style(true)) {
+        PanelResizer(panel_length=panel_length, persist=Persist::new("panel", LocalStorage), class="resizer", is_lateral=true, to_left=true, to_pixel=false)
+      }
+    }
+  });
+
+  assert!(!html.contains("width:"));
+}
+
+#[test]
+fn scrollbar_renders_initial_style() {
+  let html = sycamore::render_to_string(|| {
+    let is_scrollable = create_signal(false);
+    view! {
+      div(class="scroll") {
+        ScrollBarComponent::<_, bool>(is_lateral=true, take_orthogonal=false, is_scrollable=is_scrollable, class="bar")
+        ScrollBarComponent::<_, bool>(is_lateral=false, take_orthogonal=false, class="bar")
+      }
+    }
+  });
+
+  assert!(html.contains("width: 100.00%; left: 0.00%; touch-action: none;"));
+  assert!(html.contains("height: 100.00%; top: 0.00%; touch-action: none;"));
+}
@@ -0,0 +1,79 @@
+//! Browser tests: listeners set on mount (and on hydration)
+//! * `on_mount` only runs in the browser. Run with `wasm-pack test --headless --firefox` (add `--features hydrate` for hydration).
+
+#![cfg(target_arch = "wasm32")]
+
+use sycamore::prelude::*;
+use sycamore_seoul::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+use web_sys::{HtmlElement, KeyboardEvent, KeyboardEventInit};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+
+/// Panel of 200px in a wrap of 1000px
+fn layout<G: Html>() -> View<G> {
+  view! {
+    div(style="display: flex; width: 1000px; height: 100px;") {
+      div(class="panel", style="position: relative; width: 200px;") {
+        PanelResizer(class="resizer", is_lateral=true, to_left=false, to_pixel=true)
+      }
+      div(style="flex: 1 1 auto;")
+    }
+  }
+}
+
+/// Empty container in the document's body
+fn container() -> HtmlElement {
+  let document = gloo_utils::document();
+  let container = document.create_element("div").unwrap_throw().unchecked_into::<HtmlElement>();
+  document.body().unwrap_throw().append_child(&container).unwrap_throw();
+  container
+}
+
+/// Let queued `on_mount` callbacks run
+async fn tick() {
+  wasm_bindgen_futures::JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL)).await.unwrap_throw();
+}
+
+fn query(container: &HtmlElement, selector: &str) -> HtmlElement {
+  container.query_selector(selector).unwrap_throw().unwrap_throw().unchecked_into()
+}
+
+fn press(element: &HtmlElement, key: &str) {
+  let init = KeyboardEventInit::new();
+  init.set_key(key);
+  let e = KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init).unwrap_throw();
+  element.dispatch_event(&e).unwrap_throw();
+}
+
+
+#[wasm_bindgen_test]
+async fn panel_resizer_listens_after_mount() {
+  let container = container();
+  sycamore::render_to(layout, &container);
+  tick().await;
+
+  let (resizer, panel) = (query(&container, ".resizer"), query(&container, ".panel"));
+  assert_eq!(resizer.get_attribute("aria-valuenow").as_deref(), Some("20.00"));
+
+  // default key step: 10px
+  press(&resizer, "ArrowRight");
+  assert_eq!(panel.get_bounding_client_rect().width().round(), 210.);
+  container.remove();
+}
+
+#[cfg(feature = "hydrate")]
+#[wasm_bindgen_test]
+async fn panel_resizer_listens_after_hydration() {
+  let container = container();
+  container.set_inner_html(&sycamore::render_to_string(layout));
+  sycamore::hydrate_to(layout, &container);
+  tick().await;
+
+  let (resizer, panel) = (query(&container, ".resizer"), query(&container, ".panel"));
+  press(&resizer, "ArrowRight");
+  assert_eq!(panel.get_bounding_client_rect().width().round(), 210.);
+  container.remove();
+}
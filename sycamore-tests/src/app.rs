@@ -1,6 +1,8 @@
 mod comps;
 mod resizer;
 mod scrollbar;
+mod split;
+mod dock;
 
 use crate::*;
 use sycamore_router::{Route, Router, HistoryIntegration};
@@ -13,6 +15,8 @@ fn Index<G: Html>() -> View<G> {
         p() { a(class="index", href="/resizer") { "Test Resizer" } }
         p() { a(class="index", href="/chgcls") { "Test ChangeClass" } }
         p() { a(class="index", href="/scroll") { "Test Scroll" } }
+        p() { a(class="index", href="/split") { "Test SplitLayout" } }
+        p() { a(class="index", href="/dock") { "Test DockLayout" } }
       }
     }
   }
@@ -24,6 +28,8 @@ enum Routes {
   #[to("/resizer")] Resizer,
   #[to("/chgcls")] ChangeClass,
   #[to("/scroll")] Scroll,
+  #[to("/split")] Split,
+  #[to("/dock")] Dock,
   #[not_found]
   NotFound,
 }
@@ -34,6 +40,8 @@ fn switch<G: Html>(route: ReadSignal<Routes>) -> View<G> {
     Routes::Resizer => view! { resizer::TestResizer },
     Routes::ChangeClass => view! { comps::ChangeClassView },
     Routes::Scroll => view! { scrollbar::Scroll },
+    Routes::Split => view! { split::TestSplit },
+    Routes::Dock => view! { dock::TestDock },
     Routes::NotFound => view! { "NotFound" },
   }));
   view! { (view.get_clone()) }
@@ -0,0 +1,32 @@
+use crate::*;
+
+/// TestDock
+/// - drag tabs onto edges or centre of other tab groups
+/// 
+#[component]
+pub fn TestDock<G: Html>() -> View<G> {
+
+  let model = create_signal(DockNode::split("root", true, vec![
+    DockNode::tabs("left", vec!["files", "search"]).with_size(StyleLength::Percent(20.)),
+    DockNode::split("main", false, vec![
+      DockNode::tabs("editor", vec!["main.rs", "lib.rs"]),
+      DockNode::tabs("bottom", vec!["terminal"]).with_size(StyleLength::Percent(30.)),
+    ]),
+  ]));
+  let sizes = create_signal(HashMap::new());
+
+  let pane = move |key: &str| {
+    let label = key.to_string();
+    view! {
+      div(style="padding: 5px;") {
+        p() { (label) }
+      }
+    }
+  };
+
+  view! {
+    div(class="full") {
+      DockLayout(model=model, view=pane, sizes=sizes)
+    }
+  }
+}
@@ -143,14 +143,6 @@ fn Panel<G: Html>(
 
   // scrollbar
   let is_scrollable = create_signal(false);
-  let update_scrollbar = create_signal(false);
-
-  let window_resizing = create_signal(false);
-  listen_window_resize_event(window_resizing);
-
-  create_effect(on((window_resizing, panel_length), move || {
-    update_scrollbar.set(true);
-  }));
 
   // children
   let children = children.call();
@@ -166,6 +158,7 @@ fn Panel<G: Html>(
         min_len=min_len,
         max_len=max_len,
         change_class_on_move=change_class_on_move,
+        controlled=true,
       ) {}
 
       // in-panel
@@ -175,11 +168,10 @@ fn Panel<G: Html>(
         if is_lateral { "overflow-x" } else { "overflow-y"}
       )) {
         // ScrollBar
-        ScrollBarComponent(
+        ScrollBarComponent::<G, bool>(
           is_lateral=is_lateral,
           take_orthogonal=if is_lateral { true } else { false },
           is_scrollable=is_scrollable,
-          update_scrollbar=*update_scrollbar,
           class=scrollbar_class,
           change_on_true=(*is_scrollable, scrollbar_change_on_true.0, scrollbar_change_on_true.1)
         )
@@ -198,6 +190,7 @@ fn Panel<G: Html>(
                   }) { view! {
                     p() { "current width: " (v)}
                   }} else { view! { }})
+                  button(style="margin-left: 10px;", on:click=move |_| panel_length.set(StyleLength::Percent(30.))) { "reset" }
                 }
               }
             }
@@ -262,24 +255,15 @@ fn Parcels<G: Html>(
 
   // scrollbar
   let is_scrollable = create_signal(false);
-  let update_scrollbar = create_signal(false);
-
-  let window_resizing = create_signal(false);
-  listen_window_resize_event(window_resizing);
-
-  create_effect(on((window_resizing, parcels), move || {
-    update_scrollbar.set(true);
-  }));
 
 
   view! {
     div(class=class) {
       // ScrollBar!
-      ScrollBarComponent(
+      ScrollBarComponent::<G, bool>(
         is_lateral=is_lateral,
         take_orthogonal=false,
         is_scrollable=is_scrollable,
-        update_scrollbar=*update_scrollbar,
         class=scrollbar_class,
         change_on_true=(*is_scrollable, scrollbar_change_on_true.0, scrollbar_change_on_true.1)
       )
@@ -377,6 +361,12 @@ fn Parcel<G: Html>(
             } else {
               view! {}
             })
+            ParcelsReorderComponent(
+              order=parcels,
+              class="circle-bttn center",
+              is_lateral=is_lateral,
+              parcel_name="parcel",
+            )
             p(style="margin-left: 10px; margin-right: 10px;") {"Parcel " (p)}
             (if let Some(x) = parcel_lengths.with(|x| x.get(&p).map(|x| x.style_value())) {
               view! { p() { (current_length) (x) }}
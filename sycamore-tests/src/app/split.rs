@@ -0,0 +1,48 @@
+use crate::*;
+
+/// TestSplit
+/// 
+/// ```
+/// -----------------------------------------
+/// |        | Editor         | Outline     |
+/// | Nav    |------------------------------|
+/// |        | Terminal                     |
+/// -----------------------------------------
+/// ```
+/// 
+#[component]
+pub fn TestSplit<G: Html>() -> View<G> {
+
+  let tree = SplitNode::split("root", true, vec![
+    SplitNode::leaf("nav")
+      .with_initial(StyleLength::Percent(20.))
+      .with_min_len(StyleLength::Pixel(100.)),
+    SplitNode::split("main", false, vec![
+      SplitNode::split("top", true, vec![
+        SplitNode::leaf("editor").with_min_len(StyleLength::Pixel(100.)),
+        SplitNode::leaf("outline").with_initial(StyleLength::Percent(25.)),
+      ]),
+      SplitNode::leaf("terminal")
+        .with_initial(StyleLength::Percent(30.))
+        .with_max_len(StyleLength::Percent(80.)),
+    ]),
+  ]);
+
+  let sizes = create_signal(HashMap::new());
+
+  let leaf = move |key: &str| {
+    let (key, label) = (key.to_string(), key.to_string());
+    view! {
+      div(style="padding: 5px;") {
+        p() { (label) }
+        p() { (sizes.with(|x: &HashMap<String, StyleLength>| x.get(&key).map(|x| x.style_value()).unwrap_or_default())) }
+      }
+    }
+  };
+
+  view! {
+    div(class="full") {
+      SplitLayout(tree=tree, view=leaf, sizes=sizes)
+    }
+  }
+}